@@ -5,19 +5,184 @@
 
 #[ink::contract]
 pub mod TrabajoFinal {
-    use scale_info::prelude::format;
     use ink::prelude::string::String;
     use ink::prelude::vec::Vec;
-    use ink::prelude::string::ToString;
+    use ink::codegen::StaticEnv;
+    use ink::storage::Mapping;
+    use ink::storage::traits::StorageKey;
 
-    enum ERRORES
+    /// Errores que pueden devolver los mensajes del contrato.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Eq, Clone, Copy)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error
     {
+        /// No eres el administrador.
         NO_ES_ADMINISTRADOR,
+        /// No tienes un rol con el permiso necesario para esta operación.
+        SIN_PERMISO,
+        /// La cuenta ya tiene ese rol.
+        ROL_YA_ASIGNADO,
+        /// La cuenta no tiene ese rol.
+        ROL_NO_ASIGNADO,
+        /// No hay un traspaso de administrador en curso.
+        NO_HAY_TRASPASO,
+        /// Ya hay un traspaso de administrador en curso.
+        TRASPASO_EN_CURSO,
+        /// La cuenta propuesta ya es el administrador o el plazo de aceptación es cero.
+        TRASPASO_INVALIDO,
+        /// No eres la cuenta propuesta como administrador.
+        NO_ES_ADMINISTRADOR_PROPUESTO,
+        /// Venció el plazo para aceptar la administración.
+        TRASPASO_VENCIDO,
+        /// El traspaso todavía no fue aceptado.
+        TRASPASO_NO_ACEPTADO,
+        /// El traspaso ya fue aceptado.
+        TRASPASO_YA_ACEPTADO,
+        /// Todavía no terminó la espera del traspaso.
+        ESPERA_EN_CURSO,
+        /// Eres el administrador, no puedes registrarte.
+        ES_ADMINISTRADOR,
+        /// No estás registrado en el sistema. Espera a que te acepten en el mismo o realiza la solicitud.
         USUARIO_NO_REGISTRADO,
+        /// El registro todavía no está activado.
+        REGISTRO_NO_ACTIVADO,
+        /// El registro ya está activado.
+        REGISTRO_YA_ACTIVADO,
+        /// Tu solicitud de registro ya fue rechazada.
+        USUARIO_RECHAZADO,
+        /// Ya estás registrado como usuario.
+        USUARIO_YA_REGISTRADO,
+        /// Ya estás en la cola de usuarios pendientes.
+        USUARIO_YA_PENDIENTE,
+        /// No hay usuarios pendientes.
+        NO_HAY_USUARIOS_PENDIENTES,
+        /// No hay usuarios con tal id.
+        USUARIO_NO_ENCONTRADO,
+        /// El DNI debe tener 7 u 8 dígitos, sin puntos ni espacios, y no puede ser todo ceros.
+        /// También se devuelve si el hash del DNI está vacío.
+        DNI_INVALIDO,
+        /// Ya hay una cuenta registrada con ese DNI, pendiente, aceptada o rechazada.
+        DNI_YA_REGISTRADO,
+        /// El usuario no está pendiente de aprobación.
+        USUARIO_NO_PENDIENTE,
+        /// El usuario no tiene una solicitud de inscripción pendiente en la elección.
+        INSCRIPCION_NO_PENDIENTE,
+        /// No estás rechazado en el sistema o en la elección indicada.
+        NO_ESTA_RECHAZADO,
+        /// Ya apelaste este rechazo.
+        APELACION_YA_PRESENTADA,
+        /// El usuario no tiene una apelación pendiente en el sistema o en la elección indicada.
+        NO_HAY_APELACION_PENDIENTE,
+        /// El texto supera el largo máximo permitido.
+        TEXTO_DEMASIADO_LARGO,
+        /// Error en el formato de la fecha inicial. Formato: dd-mm-YYYY hh:mm
+        FORMATO_FECHA_INICIAL_INVALIDO,
+        /// Error en el formato de la fecha final. Formato: dd-mm-YYYY hh:mm
+        FORMATO_FECHA_FINAL_INVALIDO,
+        /// Se produjo un overflow en una operación aritmética.
+        OVERFLOW,
+        /// No existe una elección con ese id.
+        ELECCION_NO_EXISTE,
+        /// La elección ya se encuentra en el estado solicitado.
+        ELECCION_YA_EN_ESTADO,
+        /// La elección no está abierta a inscripciones.
+        ELECCION_NO_ABIERTA,
+        /// La votación en la elección ya comenzó.
+        VOTACION_INICIADA,
+        /// La votación en la elección todavía no comenzó.
+        VOTACION_NO_INICIADA,
+        /// La elección ya finalizó.
+        ELECCION_FINALIZADA,
+        /// La elección todavía no finalizó.
+        ELECCION_NO_FINALIZADA,
+        /// No existe un candidato con ese id.
+        CANDIDATO_NO_EXISTE,
+        /// No estás registrado como votante en la elección.
+        NO_ES_VOTANTE,
+        /// No eres candidato en la elección.
+        NO_ES_CANDIDATO,
+        /// No existe una lista con ese id en la elección.
+        LISTA_NO_EXISTE,
+        /// La lista debe incluir al menos un candidato.
+        LISTA_VACIA,
+        /// El candidato ya integra otra lista.
+        CANDIDATO_YA_EN_LISTA,
+        /// Ya hay una lista con ese número en la elección.
+        NUMERO_DE_LISTA_REPETIDO,
+        /// Algún candidato de la lista no declaró su género.
+        GENERO_NO_DECLARADO,
+        /// La lista no intercala los géneros de sus candidatos como exige la ley de paridad.
+        PARIDAD_INCUMPLIDA,
+        /// El candidato integra una lista: para retirarse, antes debe eliminarse la lista.
+        CANDIDATO_EN_LISTA,
+        /// La elección está ponderada por claustros y el usuario no tiene un claustro asignado.
+        SIN_CLAUSTRO,
+        /// El claustro del usuario no tiene peso en la elección.
+        CLAUSTRO_NO_PONDERADO,
+        /// La cuenta en la que quieres delegar no es votante de la elección.
+        DELEGADO_NO_ES_VOTANTE,
+        /// La cuenta en la que quieres delegar ya votó.
+        DELEGADO_YA_VOTO,
+        /// La delegación formaría un ciclo: el delegado ya delega, directa o indirectamente, en ti.
+        DELEGACION_CIRCULAR,
+        /// No delegaste tu voto en la elección.
+        NO_HAY_DELEGACION,
+        /// Ya votaste anteriormente en esta elección.
+        YA_VOTO,
+        /// Tu solicitud de inscripción en la elección fue rechazada.
+        INSCRIPCION_RECHAZADA,
+        /// Ya estás en la cola de usuarios pendientes de la elección.
+        INSCRIPCION_PENDIENTE,
+        /// Ya estás registrado como votante en la elección.
+        YA_ES_VOTANTE,
+        /// Ya has sido aceptado como candidato en la elección.
+        YA_ES_CANDIDATO,
+        /// La migración desde el almacenamiento anterior todavía está en curso.
+        MIGRACION_EN_CURSO,
+        /// La migración desde el almacenamiento anterior ya finalizó.
+        MIGRACION_FINALIZADA,
+        /// Las elecciones legadas deben importarse en orden de id.
+        ELECCION_LEGADA_FUERA_DE_ORDEN,
+        /// Solo el contrato de reportes autorizado puede realizar esta consulta.
+        NO_ES_CONTRATO_REPORTES,
+        /// La configuración de la elección no es válida.
+        CONFIGURACION_INVALIDA,
+        /// La operación no corresponde al modo o al sistema de escrutinio de la elección.
+        MODO_VOTACION_INCORRECTO,
+        /// Todavía no terminó la votación, no se pueden revelar votos.
+        REVELACION_NO_INICIADA,
+        /// La ventana de revelación ya terminó.
+        REVELACION_FINALIZADA,
+        /// No tienes un compromiso de voto sin revelar en la elección.
+        SIN_COMPROMISO,
+        /// El candidato y la sal no coinciden con el compromiso registrado.
+        COMPROMISO_INVALIDO,
+        /// La boleta debe incluir al menos un candidato.
+        BOLETA_VACIA,
+        /// La boleta incluye el mismo candidato más de una vez.
+        CANDIDATO_REPETIDO,
+        /// La boleta aprueba más candidatos de los que permite la elección.
+        DEMASIADAS_APROBACIONES,
+        /// No existe una opción con ese id en el referéndum.
+        OPCION_NO_EXISTE,
+        /// La elección todavía no tiene fechas. Es una segunda vuelta que el administrador debe programar.
+        FECHAS_NO_DEFINIDAS,
+        /// La elección no es una segunda vuelta.
+        NO_ES_SEGUNDA_VUELTA,
+        /// La segunda vuelta ya tiene fechas.
+        SEGUNDA_VUELTA_YA_PROGRAMADA,
+        /// Todavía no se terminó de copiar el padrón de la primera vuelta a la segunda.
+        PADRON_INCOMPLETO,
+        /// El cambio de estado no está permitido por la tabla de transiciones de la elección.
+        TRANSICION_INVALIDA,
+        /// Con otro votante en el claustro, el escrutinio ponderado no podría calcularse sin desbordar.
+        LIMITE_PONDERACION_EXCEDIDO,
     }
 
-    #[derive(scale::Decode, scale::Encode, Debug)]
-    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout,PartialEq,Clone))]
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
     pub enum ESTADO_ELECCION
     {
         CERRADA,
@@ -26,26 +191,503 @@ pub mod TrabajoFinal {
         FINALIZADA
     }
 
-    #[derive(scale::Decode, scale::Encode, Debug)]
-    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout,PartialEq,Clone))]
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
     pub enum TIPO_DE_USUARIO
     {
         VOTANTE,
         CANDIDATO
     }
 
-    impl ERRORES
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum ESTADO_USUARIO
     {
-        fn to_string(&self) -> String
-        {
-            match self 
-            {
-                ERRORES::NO_ES_ADMINISTRADOR => String::from("No eres el administrador."),
-                ERRORES::USUARIO_NO_REGISTRADO => String::from("No estás registrado en el sistema. Espera a que te acepten en el mismo o realiza la solicitud.")
+        PENDIENTE,
+        ACEPTADO,
+        RECHAZADO
+    }
+
+    /// Situación de un usuario dentro de una elección particular.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum ESTADO_INSCRIPCION
+    {
+        PENDIENTE,
+        VOTANTE,
+        CANDIDATO,
+        RECHAZADA
+    }
+
+    /// Claustro de la universidad al que pertenece un usuario. Lo asigna un registrador.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum CLAUSTRO
+    {
+        DOCENTES,
+        ESTUDIANTES,
+        GRADUADOS,
+        NO_DOCENTES
+    }
+
+    /// Peso de un claustro en una elección ponderada.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct PesoClaustro
+    {
+        pub claustro:CLAUSTRO,
+        pub peso:u64,
+    }
+
+    /// Id que se compromete en una elección SECRETA para votar en blanco.
+    /// Al revelar, cualquier otro id que no corresponda a un candidato cuenta como voto nulo.
+    pub const VOTO_EN_BLANCO: u64 = 0;
+
+    /// Largo máximo, en bytes, de los textos libres que se guardan en el contrato.
+    pub const LONGITUD_MAXIMA_TEXTO: usize = 500;
+    /// Largo máximo, en bytes, de la propuesta de un candidato. Para textos más largos se publica un documento externo.
+    pub const LONGITUD_MAXIMA_PROPUESTA: usize = 4000;
+    /// Cantidad máxima de posiciones que revisa una consulta paginada.
+    pub const MAXIMO_POR_PAGINA: u64 = 100;
+    /// Cantidad máxima de boletas preferenciales que lee una llamada al escrutinio. Una elección PREFERENCIAL
+    /// con más boletas, o con varias rondas, se termina de escrutar en varias llamadas.
+    pub const MAXIMO_BOLETAS_POR_LLAMADA: u64 = 2_000;
+
+    /// Título, descripción y cargo que se elige en una elección.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone, Default)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct DescripcionEleccion
+    {
+        pub titulo:String,
+        pub descripcion:String,
+        pub cargo:String,
+    }
+
+    impl DescripcionEleccion
+    {
+        fn validar(&self) -> Result<()> {
+            let textos = [&self.titulo, &self.descripcion, &self.cargo];
+            if textos.iter().any(|texto| texto.len() > LONGITUD_MAXIMA_TEXTO) { return Err(Error::TEXTO_DEMASIADO_LARGO); }
+            Ok(())
+        }
+    }
+
+    /// Plataforma que publica un candidato.
+    /// `documento` es el hash o el CID de IPFS de un documento externo con la plataforma completa.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone, Default)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct PlataformaCandidato
+    {
+        pub propuesta:String,
+        pub documento:Option<String>,
+        pub partido:Option<String>,
+    }
+
+    impl PlataformaCandidato
+    {
+        fn validar(&self) -> Result<()> {
+            if self.propuesta.len() > LONGITUD_MAXIMA_PROPUESTA { return Err(Error::TEXTO_DEMASIADO_LARGO); }
+            let textos = [&self.documento, &self.partido];
+            if textos.iter().flat_map(|texto| texto.iter()).any(|texto| texto.len() > LONGITUD_MAXIMA_TEXTO) {
+                return Err(Error::TEXTO_DEMASIADO_LARGO);
+            }
+            Ok(())
+        }
+    }
+
+    /// Cómo cuenta una opción de un referéndum.
+    /// Las abstenciones se informan pero no integran la base sobre la que se calcula la mayoría.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum SENTIDO_OPCION
+    {
+        A_FAVOR,
+        EN_CONTRA,
+        ABSTENCION
+    }
+
+    /// Opción de la boleta de un referéndum, por ejemplo ("Sí", A_FAVOR) o ("No", EN_CONTRA).
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct OpcionReferendum
+    {
+        pub texto:String,
+        pub sentido:SENTIDO_OPCION,
+    }
+
+    /// Mayoría de votos a favor que necesita un referéndum para aprobarse, sobre la base porcentual.
+    /// SIMPLE: más de la mitad. DOS_TERCIOS: al menos dos tercios. FRACCION: al menos numerador / denominador.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum MAYORIA_REQUERIDA
+    {
+        SIMPLE,
+        DOS_TERCIOS,
+        FRACCION { numerador:u64, denominador:u64 }
+    }
+
+    impl MAYORIA_REQUERIDA
+    {
+        /// Indica si los votos a favor alcanzan la mayoría. Se compara multiplicando, por lo que no hay redondeo.
+        /// Sin votos a favor nunca se alcanza.
+        fn alcanzada(&self, a_favor:u64, base:u64) -> Result<bool> {
+            let (numerador, denominador, estricta) = match self {
+                MAYORIA_REQUERIDA::SIMPLE => (1, 2, true),
+                MAYORIA_REQUERIDA::DOS_TERCIOS => (2, 3, false),
+                MAYORIA_REQUERIDA::FRACCION { numerador, denominador } => (*numerador, *denominador, false),
+            };
+            let a_favor_escalado = (a_favor as u128).checked_mul(denominador as u128).ok_or(Error::OVERFLOW)?;
+            let base_escalada = (base as u128).checked_mul(numerador as u128).ok_or(Error::OVERFLOW)?;
+            Ok(a_favor > 0 && if estricta { a_favor_escalado > base_escalada } else { a_favor_escalado >= base_escalada })
+        }
+    }
+
+    /// Pregunta, opciones y mayoría requerida de un referéndum.
+    /// Los ids de las opciones van de 1 a N en el orden de `opciones`.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Propuesta
+    {
+        pub pregunta:String,
+        pub opciones:Vec<OpcionReferendum>,
+        pub mayoria:MAYORIA_REQUERIDA,
+    }
+
+    impl Propuesta
+    {
+        /// Debe haber al menos una opción a favor y una en contra, y la fracción de la mayoría debe estar entre 0 y 1.
+        fn validar(&self) -> Result<()> {
+            if self.pregunta.len() > LONGITUD_MAXIMA_TEXTO || self.opciones.iter().any(|opcion| opcion.texto.len() > LONGITUD_MAXIMA_TEXTO) {
+                return Err(Error::TEXTO_DEMASIADO_LARGO);
+            }
+            let tiene = |sentido:SENTIDO_OPCION| self.opciones.iter().any(|opcion| opcion.sentido == sentido);
+            if self.pregunta.is_empty() || !tiene(SENTIDO_OPCION::A_FAVOR) || !tiene(SENTIDO_OPCION::EN_CONTRA) {
+                return Err(Error::CONFIGURACION_INVALIDA);
             }
+            if let MAYORIA_REQUERIDA::FRACCION { numerador, denominador } = self.mayoria {
+                if numerador == 0 || numerador > denominador { return Err(Error::CONFIGURACION_INVALIDA); }
+            }
+            Ok(())
+        }
+    }
+
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone, Default)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum CODIGO_RECHAZO
+    {
+        #[default]
+        SIN_ESPECIFICAR,
+        DATOS_INCOMPLETOS,
+        DATOS_INCORRECTOS,
+        IDENTIDAD_NO_VERIFICADA,
+        NO_CUMPLE_REQUISITOS,
+        OTRO
+    }
+
+    /// Motivo de un rechazo: un código y un detalle en texto libre.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone, Default)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct MotivoRechazo
+    {
+        pub codigo:CODIGO_RECHAZO,
+        pub detalle:String,
+    }
+
+    /// Qué hacer con un usuario que apeló su rechazo.
+    /// DENEGAR: sigue rechazado y no puede volver a apelar ese rechazo.
+    /// VOLVER_A_PENDIENTE: vuelve al final de la cola de pendientes para ser evaluado de nuevo.
+    /// ACEPTAR: queda aceptado en el sistema o inscripto en la elección.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum RESOLUCION_APELACION
+    {
+        DENEGAR,
+        VOLVER_A_PENDIENTE,
+        ACEPTAR
+    }
+
+    /// Apelación de un rechazo. Mientras `resolucion` sea None está pendiente.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Apelacion
+    {
+        pub texto:String,
+        pub fecha:u64,
+        pub resolucion:Option<RESOLUCION_APELACION>,
+        pub resuelta_por:Option<AccountId>,
+        pub fecha_resolucion:Option<u64>,
+    }
+
+    /// Rechazo de un usuario en el sistema (`eleccion_id` None) o de su inscripción en una elección.
+    /// Cada rechazo admite una sola apelación.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Rechazo
+    {
+        pub eleccion_id:Option<u64>,
+        /// Tipo de inscripción rechazada. None en los rechazos del sistema y en los importados.
+        pub tipo:Option<TIPO_DE_USUARIO>,
+        pub motivo:MotivoRechazo,
+        pub rechazado_por:AccountId,
+        pub fecha:u64,
+        pub apelacion:Option<Apelacion>,
+    }
+
+    /// Forma en que se emiten los votos de una elección.
+    /// DIRECTO: el voto se suma al candidato en el momento en que se emite.
+    /// SECRETO: durante la votación solo se registra el compromiso hash(eleccion_id, votante, candidato_id, sal) y, una vez pasada
+    /// la fecha final, cada votante lo revela dentro de la ventana de revelación para que se cuente.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum MODO_VOTACION
+    {
+        DIRECTO,
+        SECRETO
+    }
+
+    /// Cómo se determina el ganador de una elección.
+    /// MAYORIA_SIMPLE: cada votante elige un candidato y gana el más votado.
+    /// PREFERENCIAL: cada votante ordena los candidatos por preferencia y al finalizar se realiza
+    /// una segunda vuelta instantánea (ver `votar_preferencias`).
+    /// DHONDT: cada votante elige una lista de candidatos y al finalizar se reparten `bancas` entre las listas
+    /// con el método D'Hondt (ver `votar_lista`). Solo participan del reparto las listas que alcanzan el `umbral`,
+    /// expresado en centésimos de punto sobre la base porcentual: 300 equivale al 3%.
+    /// APROBACION: cada votante aprueba los candidatos que quiera, hasta `maximo_aprobaciones` si se indica,
+    /// y gana el más aprobado (ver `votar_aprobaciones`).
+    /// SCHULZE: cada votante ordena todos o algunos candidatos con `votar_preferencias`, cada boleta se suma a la
+    /// matriz de preferencias por pares y al finalizar gana el candidato que indica el método de Schulze.
+    /// REFERENDUM: no hay candidatos; cada votante elige una opción de la propuesta y al finalizar la propuesta
+    /// se aprueba o se rechaza (ver `crear_referendum` y `votar_opcion`).
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum SISTEMA_ESCRUTINIO
+    {
+        MAYORIA_SIMPLE,
+        PREFERENCIAL,
+        DHONDT { bancas:u64, umbral:u64 },
+        APROBACION { maximo_aprobaciones:Option<u64> },
+        SCHULZE,
+        REFERENDUM
+    }
+
+    /// Opciones de una elección elegidas al crearla.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct ConfiguracionEleccion
+    {
+        pub modo_votacion:MODO_VOTACION,
+        /// Duración en milisegundos de la ventana de revelación que empieza en la fecha final.
+        /// Solo se usa en modo SECRETO y debe ser mayor a cero.
+        pub duracion_revelacion:u64,
+        /// El voto SECRETO solo está disponible con MAYORIA_SIMPLE.
+        pub sistema_escrutinio:SISTEMA_ESCRUTINIO,
+        /// Reglas de balotaje, solo con MAYORIA_SIMPLE: para ganar en primera vuelta se necesita más del 45%
+        /// de los votos, o al menos el 40% con más de 10 puntos de diferencia sobre el segundo.
+        /// Si nadie lo logra, al escrutar se crea la segunda vuelta entre los dos más votados, que se programa
+        /// después de copiarle el padrón con `copiar_padron_segunda_vuelta`.
+        pub balotaje:bool,
+        /// Si es verdadero los votos en blanco integran la base sobre la que se calculan los porcentajes
+        /// del balotaje y la mayoría de la segunda vuelta instantánea. Los votos nulos nunca la integran.
+        pub blancos_en_base:bool,
+        /// Peso de cada claustro, solo con MAYORIA_SIMPLE y sin balotaje. Vacío si la elección no es ponderada.
+        /// En una elección ponderada solo se aceptan votantes de los claustros incluidos y el ganador
+        /// se determina con `escrutar_ponderado`. Cuanto más grandes los pesos y los claustros, antes se llega
+        /// al límite de votantes que admite el escrutinio (ver LIMITE_PONDERACION_EXCEDIDO).
+        pub ponderacion_claustros:Vec<PesoClaustro>,
+        /// Permite que los votantes deleguen su voto en otro votante de la elección (ver `delegar_voto`).
+        /// Solo en modo DIRECTO con MAYORIA_SIMPLE y sin ponderación por claustros.
+        pub permite_delegacion:bool,
+    }
+
+    impl Default for ConfiguracionEleccion {
+        fn default() -> Self {
+            ConfiguracionEleccion {
+                modo_votacion: MODO_VOTACION::DIRECTO,
+                duracion_revelacion: 0,
+                sistema_escrutinio: SISTEMA_ESCRUTINIO::MAYORIA_SIMPLE,
+                balotaje: false,
+                blancos_en_base: false,
+                ponderacion_claustros: Vec::new(),
+                permite_delegacion: false,
+            }
+        }
+    }
+
+    /// Traspaso de administrador en curso.
+    /// `vencimiento` es el último instante en que la cuenta propuesta puede aceptar.
+    /// `efectivo_desde` queda definido al aceptar y es el instante a partir del cual puede completarse.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct TraspasoAdministrador
+    {
+        pub propuesto:AccountId,
+        pub vencimiento:u64,
+        /// Milisegundos entre la aceptación y el momento en que el traspaso puede completarse.
+        pub espera:u64,
+        pub efectivo_desde:Option<u64>,
+    }
+
+    /// Roles que el administrador puede otorgar a otras cuentas.
+    /// REGISTRADOR: aprueba o rechaza usuarios y activa o desactiva el registro.
+    /// OFICIAL_ELECTORAL: gestiona la elección indicada y ninguna otra.
+    /// AUDITOR: consulta datos personales de los usuarios, sin modificar nada.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum ROL
+    {
+        REGISTRADOR,
+        OFICIAL_ELECTORAL(u64),
+        AUDITOR
+    }
+
+    /// Permisos que exigen los mensajes restringidos. El administrador tiene todos.
+    /// - APROBAR_USUARIOS (REGISTRADOR): obtener_informacion_siguiente_usuario_pendiente, procesar_siguiente_usuario_pendiente,
+    ///   procesar_usuario_pendiente, procesar_lote_usuarios_pendientes, rechazar_usuario_pendiente,
+    ///   obtener_datos_usuario_pendiente_por_id, obtener_historial_rechazos, asignar_claustro
+    ///   y las apelaciones de rechazos del sistema.
+    /// - GESTIONAR_REGISTRO (REGISTRADOR): activar_registro, desactivar_registro.
+    /// - LEER_DATOS_PERSONALES (AUDITOR): obtener_usuario_por_dni, obtener_dni_cifrado, obtener_posibles_duplicados
+    ///   y los datos y el género declarado de cualquier usuario.
+    /// - GESTIONAR_ELECCION(id) (OFICIAL_ELECTORAL(id)): programar_segunda_vuelta, establecer_descripcion_eleccion,
+    ///   crear_lista, eliminar_lista, cerrar/abrir/iniciar_eleccion,
+    ///   obtener_siguiente_usuario_pendiente_en_una_eleccion, procesar_usuarios_en_una_eleccion,
+    ///   procesar_usuario_en_una_eleccion, procesar_lote_en_una_eleccion, rechazar_inscripcion_en_eleccion
+    ///   y las apelaciones de rechazos en la elección.
+    /// - CREAR_ELECCIONES (solo administrador): crear_eleccion, crear_referendum.
+    /// - ADMINISTRAR_SISTEMA (solo administrador): proponer_administrador, cancelar_traspaso_administrador,
+    ///   renunciar_administracion, establecer_contrato_reportes, otorgar_rol, revocar_rol y los mensajes de migración.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum PERMISO
+    {
+        APROBAR_USUARIOS,
+        GESTIONAR_REGISTRO,
+        LEER_DATOS_PERSONALES,
+        GESTIONAR_ELECCION(u64),
+        CREAR_ELECCIONES,
+        ADMINISTRAR_SISTEMA
+    }
+
+    impl PERMISO
+    {
+        /// Rol que otorga el permiso, o None si es exclusivo del administrador.
+        fn rol_requerido(&self) -> Option<ROL> {
+            match self {
+                PERMISO::APROBAR_USUARIOS | PERMISO::GESTIONAR_REGISTRO => Some(ROL::REGISTRADOR),
+                PERMISO::LEER_DATOS_PERSONALES => Some(ROL::AUDITOR),
+                PERMISO::GESTIONAR_ELECCION(eleccion_id) => Some(ROL::OFICIAL_ELECTORAL(*eleccion_id)),
+                PERMISO::CREAR_ELECCIONES | PERMISO::ADMINISTRAR_SISTEMA => None,
+            }
+        }
+    }
+
+    /// Transiciones permitidas entre estados de una elección, como pares (desde, hacia):
+    ///   CERRADA  -> ABIERTA     el administrador abre las inscripciones, antes de la fecha de inicio.
+    ///   ABIERTA  -> CERRADA     el administrador cierra las inscripciones, antes de la fecha de inicio.
+    ///   CERRADA  -> INICIADA    se alcanzó la fecha de inicio.
+    ///   ABIERTA  -> INICIADA    se alcanzó la fecha de inicio.
+    ///   INICIADA -> FINALIZADA  pasó la fecha final.
+    /// FINALIZADA es definitivo. Cualquier otro salto, por ejemplo FINALIZADA -> ABIERTA o CERRADA -> FINALIZADA,
+    /// es rechazado. Si pasaron ambas fechas sin que nadie operara sobre la elección, se recorren
+    /// CERRADA -> INICIADA -> FINALIZADA en orden.
+    pub const TRANSICIONES_ELECCION: [(ESTADO_ELECCION, ESTADO_ELECCION); 5] = [
+        (ESTADO_ELECCION::CERRADA, ESTADO_ELECCION::ABIERTA),
+        (ESTADO_ELECCION::ABIERTA, ESTADO_ELECCION::CERRADA),
+        (ESTADO_ELECCION::CERRADA, ESTADO_ELECCION::INICIADA),
+        (ESTADO_ELECCION::ABIERTA, ESTADO_ELECCION::INICIADA),
+        (ESTADO_ELECCION::INICIADA, ESTADO_ELECCION::FINALIZADA),
+    ];
+
+    fn es_transicion_valida(desde:&ESTADO_ELECCION, hacia:&ESTADO_ELECCION) -> bool {
+        TRANSICIONES_ELECCION.iter().any(|(origen, destino)| origen == desde && destino == hacia)
+    }
+
+    /// Valida que el DNI tenga 7 u 8 dígitos y no sea todo ceros, y lo normaliza quitando los ceros a la izquierda
+    /// para que "1234567" y "01234567" se consideren el mismo DNI.
+    pub fn normalizar_dni(dni:&str) -> Result<&str> {
+        if !(7..=8).contains(&dni.len()) || !dni.bytes().all(|caracter| caracter.is_ascii_digit()) {
+            return Err(Error::DNI_INVALIDO);
+        }
+        let normalizado = dni.trim_start_matches('0');
+        if normalizado.is_empty() { return Err(Error::DNI_INVALIDO); }
+        Ok(normalizado)
+    }
+
+    /// Hash del DNI normalizado con la clave secreta del registro. Se calcula fuera de la cadena, en la aplicación
+    /// de registro, y se envía a `registrarse` en lugar del DNI. El mismo DNI siempre da el mismo hash, lo que
+    /// permite detectar duplicados, y sin la clave no se puede recuperar el DNI probando todos los valores posibles.
+    pub fn hash_dni(clave:&[u8; 32], dni:&str) -> Result<Hash> {
+        Ok(calcular_hash(&(clave, normalizar_dni(dni)?)))
+    }
+
+    /// blake2x256 de la codificación SCALE del valor.
+    fn calcular_hash<T: scale::Encode>(valor:&T) -> Hash {
+        let mut salida = <ink::env::hash::Blake2x256 as ink::env::hash::HashOutput>::Type::default();
+        ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(valor, &mut salida);
+        Hash::from(salida)
+    }
+
+    /// Ids de candidatos de una boleta preferencial, de mayor a menor preferencia.
+    type Boleta = Vec<u64>;
+
+    /// Clave de una apelación: (usuario, elección o None si apela el rechazo en el sistema).
+    type ClaveApelacion = (AccountId, Option<u64>);
+
+    /// Clave de los votos de una elección ponderada: (elección, candidato o VOTO_EN_BLANCO, claustro).
+    type ClaveVotoClaustro = (u64, u64, CLAUSTRO);
+
+    /// Votantes que delegaron su voto directamente en un mismo votante.
+    type Delegantes = Vec<AccountId>;
+
+    /// Matriz cuadrada indexada por id de candidato menos uno: la celda [a][b] corresponde al par (a + 1, b + 1).
+    /// Las filas y columnas de los candidatos retirados quedan en cero.
+    pub type MatrizPares = Vec<Vec<u64>>;
+
+    /// Posiciones de una lista guardada en un Mapping por posición: los elementos van de `inicio` a `fin - 1`
+    /// en orden de llegada. Sacar un elemento deja vacía su posición sin mover a los demás, así que una cola
+    /// conserva el orden de llegada aunque se procese algún elemento fuera de turno. `inicio` es siempre la
+    /// posición del primer elemento y `cantidad` no cuenta las posiciones vacías.
+    #[derive(scale::Decode, scale::Encode, Debug, Default, Clone, Copy, PartialEq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    struct RangoLista
+    {
+        inicio:u64,
+        fin:u64,
+        cantidad:u64,
+    }
+
+    impl RangoLista {
+        fn cantidad(&self) -> u64 {
+            self.cantidad
+        }
+        fn primera(&self) -> Option<u64> {
+            (self.cantidad > 0).then_some(self.inicio)
         }
+        /// Ocupa la posición siguiente al último elemento y la devuelve.
+        fn agregar(&mut self) -> Result<u64> {
+            let posicion = self.fin;
+            self.fin = posicion.checked_add(1).ok_or(Error::OVERFLOW)?;
+            self.cantidad = self.cantidad.checked_add(1).ok_or(Error::OVERFLOW)?;
+            Ok(posicion)
+        }
+        /// Posiciones de la página que empieza `desde` posiciones después del primero y abarca hasta
+        /// `cantidad` posiciones, nunca más de MAXIMO_POR_PAGINA. Las posiciones vacías también cuentan.
+        fn pagina(&self, desde:u64, cantidad:u64) -> core::ops::Range<u64> {
+            rango_de_pagina(self.inicio, self.fin, desde, cantidad)
+        }
+    }
+
+    /// Posiciones entre `inicio` y `fin - 1` que abarca la página que empieza `desde` posiciones después
+    /// de `inicio`, con hasta `cantidad` posiciones y nunca más de MAXIMO_POR_PAGINA.
+    fn rango_de_pagina(inicio:u64, fin:u64, desde:u64, cantidad:u64) -> core::ops::Range<u64> {
+        let inicio = inicio.saturating_add(desde).min(fin);
+        inicio..inicio.saturating_add(cantidad.min(MAXIMO_POR_PAGINA)).min(fin)
     }
 
+    /// Usuario del sistema.
+    /// Todo el storage del contrato es público: cualquiera puede leer `nombre` y `apellido` con una consulta directa
+    /// al estado de la cadena. El DNI nunca llega en claro al contrato, ni siquiera como argumento de una transacción.
+    /// Los controles de las consultas `obtener_datos_usuario_*` solo restringen lo que devuelve el contrato.
     #[derive(scale::Decode, scale::Encode, Debug)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
     struct Usuario
@@ -53,7 +695,11 @@ pub mod TrabajoFinal {
         id:AccountId,
         nombre:String,
         apellido:String,
-        dni:String,
+        /// Hash del DNI calculado fuera de la cadena con `hash_dni`.
+        dni_hash:Hash,
+        /// Copia del DNI cifrada fuera de la cadena con la clave pública del administrador, si el usuario la envió.
+        dni_cifrado:Option<Vec<u8>>,
+        estado:ESTADO_USUARIO,
     }
 
     #[derive(scale::Decode, scale::Encode, Debug)]
@@ -62,6 +708,8 @@ pub mod TrabajoFinal {
     {
         usuario_id:AccountId,
         voto_emitido:bool,
+        /// Claustro del votante al ser aceptado en una elección ponderada. None en las demás elecciones.
+        claustro:Option<CLAUSTRO>,
     }
 
     #[derive(scale::Decode, scale::Encode, Debug)]
@@ -73,793 +721,5303 @@ pub mod TrabajoFinal {
         votos_totales:u64,
     }
 
+    /// Cabecera de una elección.
+    /// Los votantes, candidatos e inscripciones se guardan en Mappings del contrato indexados por el id de la elección,
+    /// de forma que leer una elección no decodifica todo su padrón.
     #[derive(scale::Decode, scale::Encode, Debug)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
     struct Eleccion
     {
         id:u64,
-        candidatos:Vec<CandidatoConteo>,
-        votantes:Vec<Votante>,
-        usuarios_rechazados:Vec<AccountId>,
-        usuarios_pendientes:Vec<(AccountId,TIPO_DE_USUARIO)>,
-        estado: ESTADO_ELECCION, 
+        estado: ESTADO_ELECCION,
         fecha_inicio:u64,
         fecha_final:u64,
+        /// Ids de candidato asignados, incluidos los de candidatos que se retiraron.
+        cantidad_candidatos:u64,
+        candidatos_retirados:u64,
+        cantidad_votantes:u64,
+        /// Votantes del padrón que ya votaron, directamente, por delegación o comprometiendo un voto secreto.
+        votos_emitidos:u64,
+        configuracion:ConfiguracionEleccion,
+        /// Compromisos registrados en modo SECRETO y cuántos de ellos fueron revelados.
+        compromisos_emitidos:u64,
+        compromisos_revelados:u64,
+        /// Boletas emitidas en los sistemas PREFERENCIAL, APROBACION y SCHULZE. Solo se guardan las preferenciales.
+        cantidad_boletas:u64,
+        votos_en_blanco:u64,
+        votos_nulos:u64,
+        /// Ids de lista asignados (sistema DHONDT), incluidos los de listas eliminadas.
+        cantidad_listas:u64,
+        /// Verdadero una vez que se realizó el escrutinio al finalizar la elección.
+        escrutada:bool,
+        /// Candidato ganador según el escrutinio. Queda vacío si hubo empate o si se pasó a segunda vuelta.
+        ganador:Option<u64>,
+        /// Elección de la que esta es segunda vuelta.
+        eleccion_anterior:Option<u64>,
+        segunda_vuelta:Option<u64>,
     }
 
     impl Eleccion
     {
-        fn inscripcion_abierta(&self) -> bool {
-            match self.estado {
-                ESTADO_ELECCION::ABIERTA => true,
-                _ => false
-            }
-        }
         fn votacion_abierta(&self) -> bool {
-            match self.estado {
-                ESTADO_ELECCION::INICIADA => true,
-                _ => false
-            }
-        }
-        fn esta_cerrada(&self) -> bool {
-            match self.estado {
-                ESTADO_ELECCION::CERRADA => true,
-                _ => false
-            }
+            matches!(self.estado, ESTADO_ELECCION::INICIADA)
         }
         fn esta_finalizada(&self) -> bool {
+            matches!(self.estado, ESTADO_ELECCION::FINALIZADA)
+        }
+        /// Falla si la votación ya empezó.
+        fn validar_sin_iniciar(&self) -> Result<()> {
             match self.estado {
-                ESTADO_ELECCION::FINALIZADA => true,
-                _ => false
+                ESTADO_ELECCION::CERRADA | ESTADO_ELECCION::ABIERTA => Ok(()),
+                ESTADO_ELECCION::INICIADA => Err(Error::VOTACION_INICIADA),
+                ESTADO_ELECCION::FINALIZADA => Err(Error::ELECCION_FINALIZADA),
             }
         }
 
-        fn es_votante(&self, id: AccountId) -> bool {
-            self.votantes.iter().any(|vot| vot.usuario_id == id)
-        }
-        fn es_candidato(&self, id: AccountId) -> bool {
-            self.candidatos.iter().any(|cand| cand.usuario_id == id)
-        }
-
-        fn es_usuario_pendiente(&self, id: AccountId) -> bool {
-            self.usuarios_pendientes.iter().any(|(usuario_id, _tipo)| *usuario_id == id)
-        }
-        fn es_usuario_rechazado(&self, id: AccountId) -> bool {
-            self.usuarios_rechazados.iter().any(|uids| *uids == id)
+        /// Cambia el estado de la elección y emite el evento correspondiente.
+        /// Solo se permiten los cambios de `TRANSICIONES_ELECCION`.
+        fn cambiar_estado(&mut self, estado_nuevo: ESTADO_ELECCION) -> Result<()> {
+            if self.estado == estado_nuevo { return Err(Error::ELECCION_YA_EN_ESTADO); }
+            if !es_transicion_valida(&self.estado, &estado_nuevo) { return Err(Error::TRANSICION_INVALIDA); }
+            let estado_anterior = core::mem::replace(&mut self.estado, estado_nuevo.clone());
+            TrabajoFinal::env().emit_event(EstadoEleccionCambiado {
+                eleccion_id: self.id,
+                estado_anterior,
+                estado_nuevo,
+            });
+            Ok(())
         }
 
-        fn existe_candidato(&self, candidato_id:u64) -> bool
-        {
-            candidato_id >= 1 && candidato_id <= self.candidatos.len() as u64
+        /// Estado que le corresponde a la elección en `block_timestamp`.
+        /// Antes de la fecha de inicio vale el estado que eligió el administrador (CERRADA o ABIERTA).
+        /// Desde la fecha de inicio hasta la final inclusive la elección está INICIADA, y después FINALIZADA.
+        /// Una segunda vuelta sin fechas conserva su estado guardado.
+        fn estado_vigente(&self, block_timestamp:u64) -> ESTADO_ELECCION {
+            if !self.tiene_fechas() || self.esta_finalizada() { return self.estado.clone(); }
+            if block_timestamp > self.fecha_final { return ESTADO_ELECCION::FINALIZADA; }
+            if block_timestamp >= self.fecha_inicio { return ESTADO_ELECCION::INICIADA; }
+            self.estado.clone()
         }
 
-        fn obtener_informacion_candidato(&self, candidato_id:u64) -> Option<&CandidatoConteo> //Quizás se debería de cambiar a un campo específico del candidato como por ejemplo discurso/ideas, y también su nombre
-        {
-            if !self.existe_candidato(candidato_id) { return None; }
-            match (candidato_id as usize).checked_sub(1) {
-                None => None,
-                Some(index) => Some(&self.candidatos[index])
+        /// Lleva la elección a su estado vigente pasando por cada estado intermedio, de forma que
+        /// se emita un evento por cada transición. Devuelve si hubo algún cambio.
+        fn actualizar_estado(&mut self, block_timestamp:u64) -> Result<bool> {
+            let estado_vigente = self.estado_vigente(block_timestamp);
+            let hubo_cambio = self.estado != estado_vigente;
+            while self.estado != estado_vigente {
+                let siguiente = if self.votacion_abierta() { ESTADO_ELECCION::FINALIZADA } else { ESTADO_ELECCION::INICIADA };
+                self.cambiar_estado(siguiente)?;
             }
+            Ok(hubo_cambio)
         }
 
-        pub fn votar_candidato(&mut self, votante_id:AccountId, candidato_id:u64) -> Result<String, String>
+        /// Indica si el id fue asignado a algún candidato. El candidato puede haberse retirado después.
+        fn existe_candidato(&self, candidato_id:u64) -> bool
         {
-            if !self.existe_candidato(candidato_id) { return Err(String::from("No existe un candidato con este id.")); }
-
-            let votante = match self.votantes.iter_mut().find(|votante| votante.usuario_id == votante_id) {
-                Some(votante) => votante,
-                None => return Err(String::from("No estás registrado en la elección."))
-            };
-            if votante.voto_emitido { return Err(String::from("No se realizó el voto porque ya votaste anteriormente.")); }
-            
-            let candidato = match (candidato_id as usize).checked_sub(1) {
-                None => return Err(String::from("Se produjo un overflow intentando obtener el candidato.")),
-                Some(index) => &mut self.candidatos[index]
-            };
-
-            match candidato.votos_totales.checked_add(1) {
-                None => {
-                    return Err(String::from("Se produjo un overflow al intentar sumar el voto."));
-                },
-                Some(nuevo_votos_totales) => { 
-                    votante.voto_emitido = true;
-                    candidato.votos_totales = nuevo_votos_totales;
-                    return Ok(String::from("Voto emitido exitosamente."));
-                }
-            }
+            candidato_id >= 1 && candidato_id <= self.cantidad_candidatos
         }
 
-        ///Usado por el administrador.
-        ///Revisa el primer usuario pendiente.
-        ///Lo envia al Vec candidato si es candidato, o votante en caso contrario.
-        pub fn procesar_siguiente_usuario_pendiente(&mut self, aceptar_usuario:bool) -> Result<String, String>
-        {
-            let sig_usuario = self.usuarios_pendientes.first();
-            if sig_usuario.is_none() { return Err(String::from("No hay usuarios pendientes.")); }
-
-            let (usuario, tipo) = self.usuarios_pendientes.remove(0);
-            if aceptar_usuario { 
-                match tipo {
-                    TIPO_DE_USUARIO::VOTANTE =>{
-                    self.votantes.push(Votante{
-                        usuario_id:usuario,
-                        voto_emitido:false,
-                    });
-
-                   },
-                   TIPO_DE_USUARIO::CANDIDATO=>{
-                    let candidato_id_check = (self.candidatos.len() as u64).checked_add(1);
-                    let candidato_id:u64;
-                    match candidato_id_check {
-                        Some(id_validado) => candidato_id = id_validado,
-                        None => return Err(String::from("Ocurrio un overflow al calcular la ID del candidato.")),
-                    }
-                    self.candidatos.push(CandidatoConteo{
-                        id: candidato_id,
-                        usuario_id: usuario,
-                        votos_totales: 0,
-                    });
-                   },
-                }
-                Ok(String::from("Usuario agregado exitosamente."))
-            }
-            else{
-                self.usuarios_rechazados.push(usuario);
-                Ok(String::from("Usuario rechazado exitosamente."))
-            }
+        fn es_secreta(&self) -> bool {
+            self.configuracion.modo_votacion == MODO_VOTACION::SECRETO
         }
-    }
-
-    #[ink(storage)]
-    pub struct TrabajoFinal {
-        administrador:AccountId,
-        registro_activado:bool,
-        usuarios:Vec<Usuario>,
-        usuarios_pendientes:Vec<Usuario>,
-        usuarios_rechazados:Vec<Usuario>,
-        elecciones:Vec<Eleccion>,
-    }
 
-    impl TrabajoFinal {
+        /// Último instante en el que se pueden revelar votos. En modo DIRECTO coincide con la fecha final.
+        fn fin_revelacion(&self) -> u64 {
+            if !self.es_secreta() { return self.fecha_final; }
+            self.fecha_final.saturating_add(self.configuracion.duracion_revelacion)
+        }
 
-        #[ink(constructor)]
-        pub fn new() -> Self {
-            Self { 
-                administrador: Self::env().caller(),
-                registro_activado: false,
-                usuarios: Vec::new(),
-                usuarios_pendientes: Vec::new(),
-                usuarios_rechazados: Vec::new(),
-                elecciones: Vec::new(),
-            }
+        /// El conteo es definitivo cuando terminó la votación y, si la elección es secreta, también la ventana de revelación.
+        fn conteo_disponible(&self, block_timestamp:u64) -> bool {
+            !self.votacion_abierta() && (!self.es_secreta() || self.fin_revelacion() < block_timestamp)
         }
 
-        fn es_administrador(&self) -> bool {
-            self.env().caller() == self.administrador
+        /// Las segundas vueltas se crean sin fechas hasta que el administrador las programa.
+        fn tiene_fechas(&self) -> bool {
+            self.fecha_final != 0
         }
 
-        fn es_usuario_registrado(&self, id: AccountId) -> bool {
-            self.usuarios.iter().any(|usuario| usuario.id == id)
+        fn es_mayoria_simple(&self) -> bool {
+            self.configuracion.sistema_escrutinio == SISTEMA_ESCRUTINIO::MAYORIA_SIMPLE
         }
-        fn es_usuario_pendiente(&self, id: AccountId) -> bool {
-            self.usuarios_pendientes.iter().any(|usuario| usuario.id == id)
+        fn es_preferencial(&self) -> bool {
+            self.configuracion.sistema_escrutinio == SISTEMA_ESCRUTINIO::PREFERENCIAL
         }
-        fn es_usuario_rechazado(&self, id: AccountId) -> bool {
-            self.usuarios_rechazados.iter().any(|usuario| usuario.id == id)
+        fn es_dhondt(&self) -> bool {
+            matches!(self.configuracion.sistema_escrutinio, SISTEMA_ESCRUTINIO::DHONDT { .. })
         }
-
-        fn obtener_usuario_por_id(&mut self, id_usuario: AccountId) -> Option<&Usuario> {
-            if self.es_usuario_registrado(id_usuario) { 
-                    return self.usuarios.iter().find(|user| user.id == id_usuario);
-            }
-            None
+        fn es_aprobacion(&self) -> bool {
+            matches!(self.configuracion.sistema_escrutinio, SISTEMA_ESCRUTINIO::APROBACION { .. })
         }
-        fn obtener_usuario_pendiente_por_id(&mut self, id_usuario: AccountId) -> Option<&Usuario> {
-            if self.es_usuario_pendiente(id_usuario) { 
-                return self.usuarios_pendientes.iter().find(|user| user.id == id_usuario);
-            }
-            None
+        fn es_schulze(&self) -> bool {
+            self.configuracion.sistema_escrutinio == SISTEMA_ESCRUTINIO::SCHULZE
         }
-        fn obtener_usuario_rechazado_por_id(&mut self, id_usuario: AccountId) -> Option<&Usuario> {
-            if self.es_usuario_rechazado(id_usuario) { 
-                return self.usuarios_rechazados.iter().find(|user| user.id == id_usuario);
-            }
-            None
+        fn es_referendum(&self) -> bool {
+            self.configuracion.sistema_escrutinio == SISTEMA_ESCRUTINIO::REFERENDUM
         }
-
-        fn existe_eleccion(&self, eleccion_id:u64) -> bool
-        {
-            if eleccion_id >= 1 && eleccion_id <= self.elecciones.len() as u64 {
-                return true;
-            }
-            false
+        fn es_ponderada(&self) -> bool {
+            !self.configuracion.ponderacion_claustros.is_empty()
         }
 
-        fn obtener_eleccion_por_id(&mut self, eleccion_id:u64) -> Option<&mut Eleccion> {
-            if self.existe_eleccion(eleccion_id) {
-                let index = eleccion_id.checked_sub(1);
-                match index {
-                    Some(index_valid) => {
-                        return Some(&mut self.elecciones[index_valid as usize])
-                        }
-                    None => {
-                        return None
-                    }
-                }
-            }
-            return None;
+        /// Peso del claustro en la elección, o None si no participa.
+        fn peso_claustro(&self, claustro:&CLAUSTRO) -> Option<u64> {
+            self.configuracion.ponderacion_claustros.iter()
+                .find(|ponderacion| ponderacion.claustro == *claustro)
+                .map(|ponderacion| ponderacion.peso)
         }
 
-        fn validar_estado_eleccion_para_inscripciones(&mut self,eleccion_id:u64, block_timestamp:u64) -> Result<&mut Eleccion,String>{
-            let option_eleccion = self.obtener_eleccion_por_id(eleccion_id);
-            if option_eleccion.is_none() { 
-                return Err(String::from("No existe una elección con ese id."));
-            }
-            
-            let eleccion = option_eleccion.unwrap();
-            
-            if !eleccion.inscripcion_abierta() {
-                return Err(String::from("La eleccion no esta abierta, no te puedes registrar."));
-            }
-            if eleccion.fecha_final < block_timestamp {
-                if !eleccion.esta_finalizada() {
-                    eleccion.estado = ESTADO_ELECCION::FINALIZADA;
-                } 
-                return Err(String::from("La elección ya finalizó, no te puedes registrar."));
-            }
-            if eleccion.fecha_inicio < block_timestamp {
-                if !eleccion.votacion_abierta() {
-                    eleccion.estado = ESTADO_ELECCION::INICIADA;
-                }
-                return Err(String::from("La votación en la elección ya comenzó, no te puedes registrar."));
-            }
-            Ok(eleccion)
+        fn votos_sin_revelar(&self) -> u64 {
+            self.compromisos_emitidos.saturating_sub(self.compromisos_revelados)
         }
-        fn validar_estado_eleccion_para_votaciones(&mut self,eleccion_id:u64, block_timestamp:u64) -> Result<&mut Eleccion,String>{
-            let option_eleccion = self.obtener_eleccion_por_id(eleccion_id);
-            if option_eleccion.is_none() { 
-                return Err(String::from("No existe una elección con ese id."));
-            }
-            
-            let eleccion = option_eleccion.unwrap();
-            
-            if eleccion.fecha_final < block_timestamp {
-                if !eleccion.esta_finalizada() {
-                    eleccion.estado = ESTADO_ELECCION::FINALIZADA;
-                } 
-                return Err(String::from("La elección ya finalizó, no puedes votar."));
-            }
-            if eleccion.fecha_inicio > block_timestamp {
-                return Err(String::from("La eleccion no inicio, no puedes votar."));
-            }
 
-            if eleccion.fecha_inicio < block_timestamp && !eleccion.votacion_abierta() {
-                eleccion.estado = ESTADO_ELECCION::INICIADA;
-            }
-            Ok(eleccion)
+        /// Base para los porcentajes del escrutinio: los votos a candidatos y, si la configuración lo indica, los blancos.
+        fn base_porcentual(&self, votos_a_candidatos:u64) -> Result<u64> {
+            if !self.configuracion.blancos_en_base { return Ok(votos_a_candidatos); }
+            votos_a_candidatos.checked_add(self.votos_en_blanco).ok_or(Error::OVERFLOW)
         }
-
+    }
 
     // ===================================================================================================
-    // =========================creacion y administracion de usuarios=====================================
+    // =========================tipos de consulta=========================================================
     // ===================================================================================================
 
-        /// Utilizado por los usuarios para poder registrarse en el sistema.
-        /// Luego de registrarse queda pendiente de aceptación por parte de un Administrador.
-        /// Si tu registro es rechazado, no podrás volver a intentar registrarte.
-        #[ink(message)] //FUNCIONA
-        pub fn registrarse(&mut self, nombre:String, apellido:String, dni:String) -> Result<String, String>
-        {
-            if !self.registro_activado { return Err(String::from("El registro todavía no está activado.")); }
-            if self.es_administrador() { return Err(String::from("Eres el administrador, no puedes registrarte.")); }
-
-            let id = self.env().caller();
-            if self.es_usuario_rechazado(id) {  return Err(String::from("Tu solicitud de registro ya fue rechazada.")); }
-            if self.es_usuario_registrado(id) { return Err(String::from("Ya estás registrado como usuario.")); }
-            if self.es_usuario_pendiente(id) { return Err(String::from("Ya estás en la cola de usuarios pendientes.")); }
-
-            let usuario = Usuario { id, nombre, apellido, dni };
-            self.usuarios_pendientes.push(usuario);
-            return Ok(String::from("Registro exitoso. Se te añadió en la cola de usuarios pendientes."));
-        }
+    /// Datos generales de una elección.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct ResumenEleccion
+    {
+        pub id:u64,
+        pub estado:ESTADO_ELECCION,
+        pub fecha_inicio:u64,
+        pub fecha_final:u64,
+        /// Ids de candidato asignados. Los candidatos vigentes son `cantidad_candidatos - candidatos_retirados`.
+        pub cantidad_candidatos:u64,
+        pub candidatos_retirados:u64,
+        pub cantidad_votantes:u64,
+        pub votos_emitidos:u64,
+        pub modo_votacion:MODO_VOTACION,
+        pub fin_revelacion:u64,
+        /// Compromisos de voto secreto que todavía no fueron revelados.
+        /// Una vez cerrada la ventana de revelación, son los votos que quedaron sin contar.
+        pub votos_sin_revelar:u64,
+        pub sistema_escrutinio:SISTEMA_ESCRUTINIO,
+        pub balotaje:bool,
+        pub blancos_en_base:bool,
+        pub ponderacion_claustros:Vec<PesoClaustro>,
+        pub permite_delegacion:bool,
+        pub escrutada:bool,
+        pub ganador:Option<u64>,
+        pub eleccion_anterior:Option<u64>,
+        pub segunda_vuelta:Option<u64>,
+    }
 
-        /// Utilizado por un Administrador.
-        /// Obtiene la información del próximo usuario a registrarse.
-        #[ink(message)] //FUNCIONA
-        pub fn obtener_informacion_siguiente_usuario_pendiente(&self) -> Result<String, String>
-        {
-            if !self.es_administrador() { return Err(ERRORES::NO_ES_ADMINISTRADOR.to_string()); }
-            let sig_usuario = self.usuarios_pendientes.first();
-            match sig_usuario {
-                Some(usuario) => {
-                    let mut str = String::from("Nombre: ") + usuario.nombre.as_str();
-                    str.push_str((String::from("\nApellido: ") + usuario.apellido.as_str()).as_str());
-                    str.push_str((String::from("\nDNI: ") + usuario.apellido.as_str()).as_str());
-                    Ok(str)
-                },
-                None => Err(String::from("No hay usuarios pendientes.")),
-            }
-        }
+    /// Género que declara un usuario. Se usa para validar la paridad de las listas.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum GENERO
+    {
+        FEMENINO,
+        MASCULINO,
+        NO_BINARIO
+    }
 
-        /// Utilizado por un Administrador.
-        /// Se procesará el próximo usuario pendiente.
-        /// Para obtener la información del mismo, utilizar obtenerInformacionSiguienteUsuarioPendiente
-        /// Si se acepta el usuario, podrá utilizar el sistema.
-        /// Si se rechaza el usuario, este no podrá volver a intentar registrarse en el sistema.
-        #[ink(message)] //FUNCIONA
-        pub fn procesar_siguiente_usuario_pendiente(&mut self, aceptar_usuario:bool) -> Result<String, String>
-        {
-            if !self.es_administrador() { return Err(ERRORES::NO_ES_ADMINISTRADOR.to_string()); }
-            let sig_usuario = self.usuarios_pendientes.first();
-            if sig_usuario.is_none() { return Err(String::from("No hay usuarios pendientes.")); }
+    /// Candidato de una lista con el género que tenía declarado al presentarse la lista.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct MiembroLista
+    {
+        pub candidato_id:u64,
+        pub genero:GENERO,
+    }
 
-            let usuario = self.usuarios_pendientes.remove(0);
-            if aceptar_usuario { 
-                self.usuarios.push(usuario);
-                return Ok(String::from("Usuario agregado exitosamente."));
-            }
+    /// Lista de candidatos de una elección DHONDT. Los candidatos están en el orden en que ocupan las bancas.
+    /// `numero` es el número con el que la lista aparece en la boleta y no se repite dentro de la elección.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Lista
+    {
+        pub id:u64,
+        pub numero:u64,
+        pub nombre:String,
+        pub candidatos:Vec<MiembroLista>,
+    }
 
-            self.usuarios_rechazados.push(usuario);
-            return Ok(String::from("Usuario rechazado exitosamente."));
+    impl Lista
+    {
+        /// Ley de paridad de género: los candidatos deben intercalarse, por lo que dos candidatos consecutivos
+        /// no pueden tener el mismo género declarado. Como los integrantes de una lista no pueden retirarse,
+        /// una lista validada al crearse sigue cumpliéndola hasta la votación.
+        fn cumple_paridad(candidatos: &[MiembroLista]) -> bool {
+            candidatos.windows(2).all(|par| par[0].genero != par[1].genero)
         }
+    }
 
+    /// Resultado de una lista en el reparto de bancas de una elección DHONDT.
+    /// `electos` son los ids de los candidatos que obtuvieron banca, en orden de lista.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct ResultadoLista
+    {
+        pub lista_id:u64,
+        pub votos:u64,
+        pub supera_umbral:bool,
+        pub bancas:u64,
+        pub electos:Vec<u64>,
+    }
 
-       
-        #[ink(message)] //FUNCIONA
-        pub fn obtener_usuarios_pendientes(&self) -> Vec<AccountId>
-        {
-            self.usuarios_pendientes.iter().map(|usuario| usuario.id).collect()
+    /// Votos de un claustro en una elección ponderada.
+    /// `votos_validos` son los votos a candidatos del claustro más, si integran la base, sus votos en blanco.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct ConteoClaustro
+    {
+        pub claustro:CLAUSTRO,
+        pub peso:u64,
+        pub votos:Vec<ConteoRonda>,
+        pub votos_en_blanco:u64,
+        pub votos_validos:u64,
+    }
+
+    /// Resultado de un candidato en una elección ponderada.
+    /// `numerador / denominador` es la fracción exacta del total ponderado que obtuvo el candidato.
+    /// Todos los candidatos comparten denominador. `porcentaje` es esa fracción en centésimos de punto,
+    /// truncada: 3333 equivale a 33,33% aunque la fracción sea 1/3.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct ResultadoPonderado
+    {
+        pub candidato_id:u64,
+        pub numerador:u128,
+        pub denominador:u128,
+        pub porcentaje:u64,
+    }
+
+    /// Resultado de un candidato en una elección APROBACION.
+    /// `porcentaje` es la proporción de boletas que lo aprobaron, en centésimos de punto y truncada. Las boletas
+    /// incluyen los votos en blanco si integran la base, por lo que los porcentajes no suman 100%.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct ResultadoAprobacion
+    {
+        pub candidato_id:u64,
+        pub aprobaciones:u64,
+        pub porcentaje:u64,
+    }
+
+    /// Votos de una opción de un referéndum.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct ConteoOpcion
+    {
+        pub opcion_id:u64,
+        pub votos:u64,
+    }
+
+    /// Resultado de un referéndum. `base` son los votos a favor y en contra más, si integran la base, los votos en blanco.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct ResultadoReferendum
+    {
+        pub votos:Vec<ConteoOpcion>,
+        pub votos_a_favor:u64,
+        pub votos_en_contra:u64,
+        pub abstenciones:u64,
+        pub base:u64,
+        pub aprobado:bool,
+    }
+
+    /// Votos que un votante emitió en nombre de quienes delegaron en él, directa o indirectamente.
+    /// No incluye su propio voto.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct VotosDelegados
+    {
+        pub delegado:AccountId,
+        pub votos:u64,
+    }
+
+    /// Votos que no fueron para ningún candidato.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct VotosBlancosYNulos
+    {
+        pub votos_en_blanco:u64,
+        pub votos_nulos:u64,
+    }
+
+    /// Un candidato de una elección junto con los votos que recibió.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct CandidatoConVotos
+    {
+        pub id:u64,
+        pub usuario_id:AccountId,
+        pub votos_totales:u64,
+    }
+
+    /// Votos de un candidato en una ronda del escrutinio.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct ConteoRonda
+    {
+        pub candidato_id:u64,
+        pub votos:u64,
+    }
+
+    /// Una ronda de la segunda vuelta instantánea.
+    /// `conteo` tiene solo los candidatos que seguían en carrera en la ronda.
+    /// Las boletas agotadas son las que ya no tienen ningún candidato en carrera.
+    /// La última ronda tiene `ganador` si algún candidato superó la mitad de los votos válidos
+    /// y no tiene candidato eliminado. Si termina sin ganador ni eliminado, los candidatos en carrera empataron.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct RondaEscrutinio
+    {
+        pub conteo:Vec<ConteoRonda>,
+        pub boletas_agotadas:u64,
+        pub eliminado:Option<u64>,
+        pub ganador:Option<u64>,
+    }
+
+    /// Escrutinio preferencial en curso. La ronda actual ya contó las boletas de 0 a `boletas_contadas - 1`:
+    /// `votos` tiene los votos de cada candidato de `en_carrera`, en el mismo orden.
+    #[derive(scale::Decode, scale::Encode, Debug)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    struct EscrutinioPreferencial
+    {
+        en_carrera:Vec<u64>,
+        votos:Vec<u64>,
+        boletas_agotadas:u64,
+        boletas_contadas:u64,
+    }
+
+    /// Datos personales de un usuario del sistema.
+    /// Solo los puede consultar el propio usuario o una cuenta con los permisos correspondientes,
+    /// aunque siguen siendo públicos en el storage (ver `Usuario`).
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct PerfilUsuario
+    {
+        pub id:AccountId,
+        pub nombre:String,
+        pub apellido:String,
+        pub dni_hash:Hash,
+        pub estado:ESTADO_USUARIO,
+    }
+
+    /// Datos públicos de un candidato de una elección.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct InformacionCandidato
+    {
+        pub candidato_id:u64,
+        pub usuario_id:AccountId,
+        pub nombre:String,
+        pub apellido:String,
+        /// None si el candidato todavía no publicó su plataforma.
+        pub plataforma:Option<PlataformaCandidato>,
+    }
+
+    /// Una solicitud de inscripción pendiente en una elección.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct SolicitudInscripcion
+    {
+        pub usuario_id:AccountId,
+        pub tipo:TIPO_DE_USUARIO,
+    }
+
+    /// Cuenta importada del almacenamiento anterior con el mismo DNI que otra cuenta ya registrada.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct PosibleDuplicado
+    {
+        pub cuenta:AccountId,
+        /// Primera cuenta registrada con ese DNI.
+        pub cuenta_original:AccountId,
+    }
+
+    /// Apelación todavía sin resolver.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct ApelacionPendiente
+    {
+        pub usuario:AccountId,
+        pub eleccion_id:Option<u64>,
+    }
+
+    /// Resultado de procesar un usuario dentro de un lote.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct ResultadoProcesamiento
+    {
+        pub usuario:AccountId,
+        pub resultado:Result<()>,
+    }
+
+    /// Un votante del padrón de una elección y si ya emitió su voto.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct EstadoVotante
+    {
+        pub usuario_id:AccountId,
+        pub voto_emitido:bool,
+        pub claustro:Option<CLAUSTRO>,
+    }
+
+    impl From<&Eleccion> for ResumenEleccion {
+        fn from(eleccion: &Eleccion) -> Self {
+            ResumenEleccion {
+                id: eleccion.id,
+                estado: eleccion.estado.clone(),
+                fecha_inicio: eleccion.fecha_inicio,
+                fecha_final: eleccion.fecha_final,
+                cantidad_candidatos: eleccion.cantidad_candidatos,
+                candidatos_retirados: eleccion.candidatos_retirados,
+                cantidad_votantes: eleccion.cantidad_votantes,
+                votos_emitidos: eleccion.votos_emitidos,
+                modo_votacion: eleccion.configuracion.modo_votacion.clone(),
+                fin_revelacion: eleccion.fin_revelacion(),
+                votos_sin_revelar: eleccion.votos_sin_revelar(),
+                sistema_escrutinio: eleccion.configuracion.sistema_escrutinio.clone(),
+                balotaje: eleccion.configuracion.balotaje,
+                blancos_en_base: eleccion.configuracion.blancos_en_base,
+                ponderacion_claustros: eleccion.configuracion.ponderacion_claustros.clone(),
+                permite_delegacion: eleccion.configuracion.permite_delegacion,
+                escrutada: eleccion.escrutada,
+                ganador: eleccion.ganador,
+                eleccion_anterior: eleccion.eleccion_anterior,
+                segunda_vuelta: eleccion.segunda_vuelta,
+            }
+        }
+    }
+
+    impl From<Usuario> for PerfilUsuario {
+        fn from(usuario: Usuario) -> Self {
+            PerfilUsuario {
+                id: usuario.id,
+                nombre: usuario.nombre,
+                apellido: usuario.apellido,
+                dni_hash: usuario.dni_hash,
+                estado: usuario.estado,
+            }
+        }
+    }
+
+    impl From<CandidatoConteo> for CandidatoConVotos {
+        fn from(candidato: CandidatoConteo) -> Self {
+            CandidatoConVotos {
+                id: candidato.id,
+                usuario_id: candidato.usuario_id,
+                votos_totales: candidato.votos_totales,
+            }
+        }
+    }
+
+    impl From<Votante> for EstadoVotante {
+        fn from(votante: Votante) -> Self {
+            EstadoVotante {
+                usuario_id: votante.usuario_id,
+                voto_emitido: votante.voto_emitido,
+                claustro: votante.claustro,
+            }
+        }
+    }
+
+    // ===================================================================================================
+    // =========================migracion desde el almacenamiento anterior================================
+    // ===================================================================================================
+    //
+    // Las versiones anteriores del contrato guardaban todo el estado en Vecs dentro de la raíz del storage.
+    // Los tipos *Legado reproducen exactamente esa codificación SCALE, por lo que la raíz del contrato viejo
+    // puede decodificarse fuera de la cadena como `EstadoLegado` y reenviarse al contrato nuevo:
+    //   1. Desplegar el contrato nuevo con el constructor `nuevo_para_migracion`.
+    //   2. Convertir los usuarios con `UsuarioImportado::desde_legado` e importarlos por lotes con
+    //      `importar_usuarios_legado`.
+    //   3. Importar las elecciones en orden de id con `importar_eleccion_legada`.
+    //   4. Llamar a `finalizar_migracion`. A partir de ahí no se aceptan más importaciones.
+
+    #[derive(scale::Decode, scale::Encode, Debug)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct UsuarioLegado
+    {
+        pub id:AccountId,
+        pub nombre:String,
+        pub apellido:String,
+        pub dni:String,
+    }
+
+    /// Usuario a importar con `importar_usuarios_legado`. Se arma fuera de la cadena a partir de un `UsuarioLegado`
+    /// con `desde_legado`, para no volver a enviar los DNI en claro.
+    #[derive(scale::Decode, scale::Encode, Debug)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct UsuarioImportado
+    {
+        pub id:AccountId,
+        pub nombre:String,
+        pub apellido:String,
+        pub dni_hash:Hash,
+    }
+
+    impl UsuarioImportado {
+        /// Calcula el hash del DNI con la clave del registro. Los DNI legados no se validaban: los que no tienen
+        /// formato válido se hashean tal cual, así que solo coinciden con otro DNI legado idéntico.
+        pub fn desde_legado(usuario:UsuarioLegado, clave:&[u8; 32]) -> Self {
+            let dni_hash = hash_dni(clave, &usuario.dni).unwrap_or_else(|_| calcular_hash(&(clave, usuario.dni.as_str())));
+            UsuarioImportado { id: usuario.id, nombre: usuario.nombre, apellido: usuario.apellido, dni_hash }
+        }
+    }
+
+    #[derive(scale::Decode, scale::Encode, Debug)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct VotanteLegado
+    {
+        pub usuario_id:AccountId,
+        pub voto_emitido:bool,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Debug)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct CandidatoConteoLegado
+    {
+        pub id:u64,
+        pub usuario_id:AccountId,
+        pub votos_totales:u64,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Debug)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct EleccionLegada
+    {
+        pub id:u64,
+        pub candidatos:Vec<CandidatoConteoLegado>,
+        pub votantes:Vec<VotanteLegado>,
+        pub usuarios_rechazados:Vec<AccountId>,
+        pub usuarios_pendientes:Vec<(AccountId,TIPO_DE_USUARIO)>,
+        pub estado: ESTADO_ELECCION,
+        pub fecha_inicio:u64,
+        pub fecha_final:u64,
+    }
+
+    /// Raíz del storage de las versiones anteriores del contrato.
+    #[derive(scale::Decode, scale::Encode, Debug)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct EstadoLegado
+    {
+        pub administrador:AccountId,
+        pub registro_activado:bool,
+        pub usuarios:Vec<UsuarioLegado>,
+        pub usuarios_pendientes:Vec<UsuarioLegado>,
+        pub usuarios_rechazados:Vec<UsuarioLegado>,
+        pub elecciones:Vec<EleccionLegada>,
+    }
+
+    // ===================================================================================================
+    // =========================eventos===================================================================
+    // ===================================================================================================
+
+    /// Un usuario solicitó registrarse en el sistema y quedó pendiente de aprobación.
+    #[ink(event)]
+    pub struct UsuarioRegistrado {
+        #[ink(topic)]
+        usuario: AccountId,
+    }
+
+    /// Un administrador aceptó el registro de un usuario.
+    #[ink(event)]
+    pub struct UsuarioAprobado {
+        #[ink(topic)]
+        usuario: AccountId,
+    }
+
+    /// Un administrador rechazó el registro de un usuario.
+    #[ink(event)]
+    pub struct UsuarioRechazado {
+        #[ink(topic)]
+        usuario: AccountId,
+        codigo: CODIGO_RECHAZO,
+    }
+
+    /// Se activó o desactivó el registro de usuarios.
+    #[ink(event)]
+    pub struct RegistroCambiado {
+        activado: bool,
+    }
+
+    /// Se asignó o quitó el claustro de un usuario.
+    #[ink(event)]
+    pub struct ClaustroAsignado {
+        #[ink(topic)]
+        usuario: AccountId,
+        claustro: Option<CLAUSTRO>,
+    }
+
+    /// Se creó una elección nueva en estado CERRADA.
+    #[ink(event)]
+    pub struct EleccionCreada {
+        #[ink(topic)]
+        eleccion_id: u64,
+        fecha_inicio: u64,
+        fecha_final: u64,
+    }
+
+    /// Una elección pasó de un estado a otro, ya sea por un administrador o por las fechas de la misma.
+    #[ink(event)]
+    pub struct EstadoEleccionCambiado {
+        #[ink(topic)]
+        eleccion_id: u64,
+        estado_anterior: ESTADO_ELECCION,
+        estado_nuevo: ESTADO_ELECCION,
+    }
+
+    /// Un usuario solicitó inscribirse en una elección como votante o candidato.
+    #[ink(event)]
+    pub struct InscripcionSolicitada {
+        #[ink(topic)]
+        eleccion_id: u64,
+        #[ink(topic)]
+        usuario: AccountId,
+        tipo: TIPO_DE_USUARIO,
+    }
+
+    /// Un administrador aceptó la inscripción de un usuario en una elección.
+    /// Si el usuario es candidato se informa el id de candidato asignado.
+    #[ink(event)]
+    pub struct InscripcionAceptada {
+        #[ink(topic)]
+        eleccion_id: u64,
+        #[ink(topic)]
+        usuario: AccountId,
+        tipo: TIPO_DE_USUARIO,
+        candidato_id: Option<u64>,
+    }
+
+    /// Un administrador rechazó la inscripción de un usuario en una elección.
+    #[ink(event)]
+    pub struct InscripcionRechazada {
+        #[ink(topic)]
+        eleccion_id: u64,
+        #[ink(topic)]
+        usuario: AccountId,
+        codigo: CODIGO_RECHAZO,
+    }
+
+    /// Un usuario rechazado apeló. `eleccion_id` es None si apeló el rechazo de su registro.
+    #[ink(event)]
+    pub struct ApelacionPresentada {
+        #[ink(topic)]
+        usuario: AccountId,
+        eleccion_id: Option<u64>,
+    }
+
+    /// Se resolvió la apelación de un usuario.
+    #[ink(event)]
+    pub struct ApelacionResuelta {
+        #[ink(topic)]
+        usuario: AccountId,
+        eleccion_id: Option<u64>,
+        resolucion: RESOLUCION_APELACION,
+    }
+
+    /// Un candidato se retiró de una elección antes de la votación.
+    #[ink(event)]
+    pub struct CandidaturaRetirada {
+        #[ink(topic)]
+        eleccion_id: u64,
+        #[ink(topic)]
+        usuario: AccountId,
+        candidato_id: u64,
+    }
+
+    /// Un votante se dio de baja del padrón de una elección antes de la votación.
+    #[ink(event)]
+    pub struct VotanteDadoDeBaja {
+        #[ink(topic)]
+        eleccion_id: u64,
+        #[ink(topic)]
+        usuario: AccountId,
+    }
+
+    /// Se estableció o cambió el título, la descripción o el cargo de una elección.
+    #[ink(event)]
+    pub struct DescripcionEleccionCambiada {
+        #[ink(topic)]
+        eleccion_id: u64,
+    }
+
+    /// Un candidato publicó o actualizó su plataforma.
+    #[ink(event)]
+    pub struct PlataformaPublicada {
+        #[ink(topic)]
+        eleccion_id: u64,
+        candidato_id: u64,
+    }
+
+    /// Se creó una lista de candidatos en una elección DHONDT.
+    #[ink(event)]
+    pub struct ListaCreada {
+        #[ink(topic)]
+        eleccion_id: u64,
+        lista_id: u64,
+        numero: u64,
+    }
+
+    /// Se eliminó una lista de candidatos.
+    #[ink(event)]
+    pub struct ListaEliminada {
+        #[ink(topic)]
+        eleccion_id: u64,
+        lista_id: u64,
+    }
+
+    /// Un votante delegó su voto en otro votante de la elección.
+    #[ink(event)]
+    pub struct VotoDelegado {
+        #[ink(topic)]
+        eleccion_id: u64,
+        #[ink(topic)]
+        votante: AccountId,
+        delegado: AccountId,
+    }
+
+    /// Quedó sin efecto la delegación de un votante, porque la revocó o porque votó por sí mismo.
+    #[ink(event)]
+    pub struct DelegacionRevocada {
+        #[ink(topic)]
+        eleccion_id: u64,
+        #[ink(topic)]
+        votante: AccountId,
+        delegado: AccountId,
+    }
+
+    /// Un votante emitió su voto. No se informa el candidato elegido.
+    #[ink(event)]
+    pub struct VotoEmitido {
+        #[ink(topic)]
+        eleccion_id: u64,
+        #[ink(topic)]
+        votante: AccountId,
+    }
+
+    /// Un votante reveló su voto secreto y este fue contado. No se informa el candidato elegido.
+    #[ink(event)]
+    pub struct VotoRevelado {
+        #[ink(topic)]
+        eleccion_id: u64,
+        #[ink(topic)]
+        votante: AccountId,
+    }
+
+    /// Se realizó el escrutinio de una elección.
+    #[ink(event)]
+    pub struct EleccionEscrutada {
+        #[ink(topic)]
+        eleccion_id: u64,
+        ganador: Option<u64>,
+    }
+
+    /// Se escrutó un referéndum.
+    #[ink(event)]
+    pub struct ReferendumEscrutado {
+        #[ink(topic)]
+        eleccion_id: u64,
+        aprobado: bool,
+    }
+
+    /// Nadie alcanzó los votos necesarios para ganar en primera vuelta y se creó la segunda vuelta.
+    #[ink(event)]
+    pub struct SegundaVueltaCreada {
+        #[ink(topic)]
+        eleccion_id: u64,
+        #[ink(topic)]
+        segunda_vuelta_id: u64,
+    }
+
+    /// El administrador estableció las fechas de una segunda vuelta.
+    #[ink(event)]
+    pub struct SegundaVueltaProgramada {
+        #[ink(topic)]
+        eleccion_id: u64,
+        fecha_inicio: u64,
+        fecha_final: u64,
+    }
+
+    /// El rol de administrador pasó a otra cuenta.
+    #[ink(event)]
+    pub struct AdministradorTransferido {
+        #[ink(topic)]
+        anterior: AccountId,
+        #[ink(topic)]
+        nuevo: AccountId,
+    }
+
+    /// El administrador otorgó un rol a una cuenta.
+    #[ink(event)]
+    pub struct RolOtorgado {
+        #[ink(topic)]
+        cuenta: AccountId,
+        rol: ROL,
+    }
+
+    /// El administrador revocó un rol a una cuenta.
+    #[ink(event)]
+    pub struct RolRevocado {
+        #[ink(topic)]
+        cuenta: AccountId,
+        rol: ROL,
+    }
+
+    /// El administrador propuso a otra cuenta como sucesora.
+    #[ink(event)]
+    pub struct TraspasoAdministradorPropuesto {
+        #[ink(topic)]
+        propuesto: AccountId,
+        vencimiento: u64,
+        espera: u64,
+    }
+
+    /// La cuenta propuesta aceptó la administración, que pasará a ella desde `efectivo_desde`.
+    #[ink(event)]
+    pub struct TraspasoAdministradorAceptado {
+        #[ink(topic)]
+        propuesto: AccountId,
+        efectivo_desde: u64,
+    }
+
+    /// El administrador canceló el traspaso propuesto.
+    #[ink(event)]
+    pub struct TraspasoAdministradorCancelado {
+        #[ink(topic)]
+        propuesto: AccountId,
+    }
+
+    /// El administrador renunció sin sucesor.
+    #[ink(event)]
+    pub struct AdministracionRenunciada {
+        #[ink(topic)]
+        anterior: AccountId,
+    }
+
+    /// Cambió el contrato autorizado a consultar la API de reportes.
+    #[ink(event)]
+    pub struct ContratoReportesCambiado {
+        contrato: Option<AccountId>,
+    }
+
+    #[ink(storage)]
+    pub struct TrabajoFinal {
+        /// None si el administrador renunció.
+        administrador:Option<AccountId>,
+        traspaso_administrador:Option<TraspasoAdministrador>,
+        /// Roles otorgados por el administrador a cada cuenta.
+        roles:Mapping<AccountId, Vec<ROL>>,
+        registro_activado:bool,
+        /// Mientras sea verdadero se aceptan importaciones del almacenamiento anterior.
+        migracion_en_curso:bool,
+        usuarios:Mapping<AccountId, Usuario>,
+        usuario_por_dni:Mapping<Hash, AccountId>,
+        /// Género declarado por cada usuario.
+        generos:Mapping<AccountId, GENERO>,
+        /// Claustro asignado a cada usuario.
+        claustros:Mapping<AccountId, CLAUSTRO>,
+        /// Usuarios de cada estado por (estado, posición), la posición de cada usuario en la lista de su estado
+        /// y las posiciones ocupadas de cada lista (ver `RangoLista`). La de pendientes es la cola de aprobación.
+        usuarios_por_posicion:Mapping<(ESTADO_USUARIO, u64), AccountId>,
+        posicion_de_usuario:Mapping<AccountId, u64>,
+        rangos_usuarios:Mapping<ESTADO_USUARIO, RangoLista>,
+        /// Rechazos de cada usuario, en el sistema y en las elecciones, con sus apelaciones. Nunca se borran.
+        historial_rechazos:Mapping<AccountId, Vec<Rechazo>>,
+        /// Apelaciones sin resolver por posición y la posición de cada una por (usuario, elección).
+        apelaciones_pendientes:Mapping<u64, ApelacionPendiente>,
+        posicion_de_apelacion:Mapping<ClaveApelacion, u64>,
+        rango_apelaciones:RangoLista,
+        /// Cuentas que comparten DNI con otra. Solo pueden surgir al importar usuarios legados,
+        /// ya que `registrarse` rechaza los DNI repetidos.
+        posibles_duplicados:Mapping<u64, PosibleDuplicado>,
+        rango_posibles_duplicados:RangoLista,
+        elecciones:Mapping<u64, Eleccion>,
+        /// Título, descripción y cargo de cada elección. Se guardan aparte para que leer una elección no los decodifique.
+        descripciones:Mapping<u64, DescripcionEleccion>,
+        cantidad_elecciones:u64,
+        inscripciones:Mapping<(u64, AccountId), ESTADO_INSCRIPCION>,
+        /// Cola de solicitudes de inscripción de cada elección por (elección, posición), y la posición de cada usuario.
+        inscripciones_pendientes:Mapping<(u64, u64), SolicitudInscripcion>,
+        posicion_de_inscripcion:Mapping<(u64, AccountId), u64>,
+        rangos_inscripciones:Mapping<u64, RangoLista>,
+        votantes:Mapping<(u64, AccountId), Votante>,
+        /// Posición dentro del padrón de la elección -> votante. Las posiciones van de 0 a cantidad_votantes - 1.
+        votantes_por_indice:Mapping<(u64, u64), AccountId>,
+        indice_de_votante:Mapping<(u64, AccountId), u64>,
+        /// Las IDs de los candidatos van de 1 a N dentro de cada elección.
+        candidatos:Mapping<(u64, u64), CandidatoConteo>,
+        candidato_por_usuario:Mapping<(u64, AccountId), u64>,
+        /// Plataforma publicada por cada candidato, por (elección, id de candidato).
+        plataformas:Mapping<(u64, u64), PlataformaCandidato>,
+        /// Compromisos de voto secreto todavía no revelados.
+        compromisos:Mapping<(u64, AccountId), Hash>,
+        /// Boletas de las elecciones preferenciales, por posición dentro de la elección.
+        /// Son anónimas: no se guarda qué votante emitió cada una.
+        boletas:Mapping<(u64, u64), Boleta>,
+        rondas_escrutinio:Mapping<u64, Vec<RondaEscrutinio>>,
+        /// Escrutinios preferenciales que todavía no terminaron, por elección.
+        escrutinios_preferenciales:Mapping<u64, EscrutinioPreferencial>,
+        /// Listas de las elecciones DHONDT, por (elección, id de lista), y la lista de cada candidato.
+        listas:Mapping<(u64, u64), Lista>,
+        lista_de_candidato:Mapping<(u64, u64), u64>,
+        votos_por_lista:Mapping<(u64, u64), u64>,
+        reparto_bancas:Mapping<u64, Vec<ResultadoLista>>,
+        resultados_aprobacion:Mapping<u64, Vec<ResultadoAprobacion>>,
+        /// Matriz de preferencias por pares de las elecciones SCHULZE: la celda [a][b] cuenta los votantes que
+        /// prefieren al candidato a + 1 sobre el b + 1. Se actualiza con cada boleta.
+        matrices_pares:Mapping<u64, MatrizPares>,
+        /// Fuerza del camino más fuerte entre cada par de candidatos, calculada al escrutar.
+        caminos_mas_fuertes:Mapping<u64, MatrizPares>,
+        /// Propuesta de cada referéndum, votos por (elección, id de opción) y resultado.
+        propuestas:Mapping<u64, Propuesta>,
+        votos_por_opcion:Mapping<(u64, u64), u64>,
+        resultados_referendum:Mapping<u64, ResultadoReferendum>,
+        /// Delegaciones de voto por (elección, votante) -> delegado, y quiénes delegaron en cada votante.
+        /// Las delegaciones que se usaron al votar el delegado se conservan.
+        delegaciones:Mapping<(u64, AccountId), AccountId>,
+        delegantes:Mapping<(u64, AccountId), Delegantes>,
+        /// Votos delegados que emitió cada votante al votar.
+        votos_delegados:Mapping<(u64, AccountId), u64>,
+        /// Votos de las elecciones ponderadas por claustro.
+        votos_por_claustro:Mapping<ClaveVotoClaustro, u64>,
+        /// Votantes de cada claustro en el padrón de una elección ponderada.
+        votantes_por_claustro:Mapping<(u64, CLAUSTRO), u64>,
+        resultados_ponderados:Mapping<u64, Vec<ResultadoPonderado>>,
+        /// Único contrato que puede consultar la API de reportes.
+        contrato_reportes:Option<AccountId>,
+    }
+
+    impl Default for TrabajoFinal {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl TrabajoFinal {
+
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {
+                administrador: Some(Self::env().caller()),
+                traspaso_administrador: None,
+                roles: Mapping::default(),
+                registro_activado: false,
+                migracion_en_curso: false,
+                usuarios: Mapping::default(),
+                usuario_por_dni: Mapping::default(),
+                generos: Mapping::default(),
+                claustros: Mapping::default(),
+                usuarios_por_posicion: Mapping::default(),
+                posicion_de_usuario: Mapping::default(),
+                rangos_usuarios: Mapping::default(),
+                historial_rechazos: Mapping::default(),
+                apelaciones_pendientes: Mapping::default(),
+                posicion_de_apelacion: Mapping::default(),
+                rango_apelaciones: RangoLista::default(),
+                posibles_duplicados: Mapping::default(),
+                rango_posibles_duplicados: RangoLista::default(),
+                elecciones: Mapping::default(),
+                descripciones: Mapping::default(),
+                cantidad_elecciones: 0,
+                inscripciones: Mapping::default(),
+                inscripciones_pendientes: Mapping::default(),
+                posicion_de_inscripcion: Mapping::default(),
+                rangos_inscripciones: Mapping::default(),
+                votantes: Mapping::default(),
+                votantes_por_indice: Mapping::default(),
+                indice_de_votante: Mapping::default(),
+                candidatos: Mapping::default(),
+                candidato_por_usuario: Mapping::default(),
+                plataformas: Mapping::default(),
+                compromisos: Mapping::default(),
+                boletas: Mapping::default(),
+                rondas_escrutinio: Mapping::default(),
+                escrutinios_preferenciales: Mapping::default(),
+                listas: Mapping::default(),
+                lista_de_candidato: Mapping::default(),
+                votos_por_lista: Mapping::default(),
+                reparto_bancas: Mapping::default(),
+                resultados_aprobacion: Mapping::default(),
+                matrices_pares: Mapping::default(),
+                caminos_mas_fuertes: Mapping::default(),
+                propuestas: Mapping::default(),
+                votos_por_opcion: Mapping::default(),
+                resultados_referendum: Mapping::default(),
+                delegaciones: Mapping::default(),
+                delegantes: Mapping::default(),
+                votos_delegados: Mapping::default(),
+                votos_por_claustro: Mapping::default(),
+                votantes_por_claustro: Mapping::default(),
+                resultados_ponderados: Mapping::default(),
+                contrato_reportes: None,
+            }
+        }
+
+        /// Crea el contrato listo para recibir el estado de una versión anterior.
+        /// Ver `importar_usuarios_legado`, `importar_eleccion_legada` y `finalizar_migracion`.
+        #[ink(constructor)]
+        pub fn nuevo_para_migracion() -> Self {
+            let mut contrato = Self::new();
+            contrato.migracion_en_curso = true;
+            contrato
+        }
+
+        fn es_administrador(&self) -> bool {
+            self.administrador == Some(self.env().caller())
+        }
+
+        fn completar_traspaso(&mut self, nuevo: AccountId) -> Result<()> {
+            let anterior = self.administrador.ok_or(Error::NO_ES_ADMINISTRADOR)?;
+            self.administrador = Some(nuevo);
+            self.traspaso_administrador = None;
+            self.env().emit_event(AdministradorTransferido { anterior, nuevo });
+            Ok(())
+        }
+
+        fn tiene_rol(&self, cuenta: AccountId, rol: &ROL) -> bool {
+            self.roles.get(cuenta).is_some_and(|roles| roles.contains(rol))
+        }
+
+        fn cuenta_tiene_permiso(&self, cuenta: AccountId, permiso: &PERMISO) -> bool {
+            if self.administrador == Some(cuenta) { return true; }
+            permiso.rol_requerido().is_some_and(|rol| self.tiene_rol(cuenta, &rol))
+        }
+
+        /// Verifica que quien llama tenga el permiso. Los permisos exclusivos del administrador
+        /// devuelven NO_ES_ADMINISTRADOR y el resto SIN_PERMISO.
+        fn verificar_permiso(&self, permiso: PERMISO) -> Result<()> {
+            if self.cuenta_tiene_permiso(self.env().caller(), &permiso) { return Ok(()); }
+            match permiso.rol_requerido() {
+                None => Err(Error::NO_ES_ADMINISTRADOR),
+                Some(_) => Err(Error::SIN_PERMISO),
+            }
+        }
+
+        fn es_contrato_reportes(&self) -> bool {
+            self.contrato_reportes == Some(self.env().caller())
+        }
+
+        fn estado_usuario(&self, id: AccountId) -> Option<ESTADO_USUARIO> {
+            self.usuarios.get(id).map(|usuario| usuario.estado)
+        }
+        fn es_usuario_registrado(&self, id: AccountId) -> bool {
+            self.estado_usuario(id) == Some(ESTADO_USUARIO::ACEPTADO)
+        }
+        fn es_usuario_pendiente(&self, id: AccountId) -> bool {
+            self.estado_usuario(id) == Some(ESTADO_USUARIO::PENDIENTE)
+        }
+        fn es_usuario_rechazado(&self, id: AccountId) -> bool {
+            self.estado_usuario(id) == Some(ESTADO_USUARIO::RECHAZADO)
+        }
+
+        fn obtener_usuario_con_estado(&self, id_usuario: AccountId, estado: ESTADO_USUARIO) -> Option<Usuario> {
+            self.usuarios.get(id_usuario).filter(|usuario| usuario.estado == estado)
+        }
+        fn obtener_usuario_por_id(&self, id_usuario: AccountId) -> Option<Usuario> {
+            self.obtener_usuario_con_estado(id_usuario, ESTADO_USUARIO::ACEPTADO)
+        }
+        fn obtener_usuario_pendiente_por_id(&self, id_usuario: AccountId) -> Option<Usuario> {
+            self.obtener_usuario_con_estado(id_usuario, ESTADO_USUARIO::PENDIENTE)
+        }
+        fn obtener_usuario_rechazado_por_id(&self, id_usuario: AccountId) -> Option<Usuario> {
+            self.obtener_usuario_con_estado(id_usuario, ESTADO_USUARIO::RECHAZADO)
+        }
+
+        /// Saca el elemento en `posicion` de una lista guardada por posición dejando vacía su posición.
+        /// Si era el primero, el inicio pasa al siguiente elemento salteando las posiciones ya vacías.
+        fn quitar_de_lista<C, V, K>(lista: &mut Mapping<C, V, K>, clave: impl Fn(u64) -> C, rango: &mut RangoLista, posicion:u64) -> Result<()>
+        where C: scale::EncodeLike, V: ink::storage::traits::Packed + scale::EncodeLike, K: StorageKey
+        {
+            lista.remove(clave(posicion));
+            rango.cantidad = rango.cantidad.checked_sub(1).ok_or(Error::OVERFLOW)?;
+            if rango.cantidad == 0 {
+                rango.inicio = rango.fin;
+            } else if posicion == rango.inicio {
+                rango.inicio = posicion.checked_add(1).ok_or(Error::OVERFLOW)?;
+                while !lista.contains(clave(rango.inicio)) {
+                    rango.inicio = rango.inicio.checked_add(1).ok_or(Error::OVERFLOW)?;
+                }
+            }
+            Ok(())
+        }
+        fn pagina_de_lista<C, V, K>(lista: &Mapping<C, V, K>, clave: impl Fn(u64) -> C, rango: RangoLista, desde:u64, cantidad:u64) -> Vec<V>
+        where C: scale::EncodeLike, V: ink::storage::traits::Packed + scale::EncodeLike, K: StorageKey
+        {
+            rango.pagina(desde, cantidad).filter_map(|posicion| lista.get(clave(posicion))).collect()
+        }
+
+        fn rango_usuarios(&self, estado: &ESTADO_USUARIO) -> RangoLista {
+            self.rangos_usuarios.get(estado).unwrap_or_default()
+        }
+        fn primer_usuario_pendiente(&self) -> Option<AccountId> {
+            let posicion = self.rango_usuarios(&ESTADO_USUARIO::PENDIENTE).primera()?;
+            self.usuarios_por_posicion.get((ESTADO_USUARIO::PENDIENTE, posicion))
+        }
+        fn pagina_de_usuarios(&self, estado: ESTADO_USUARIO, desde:u64, cantidad:u64) -> Vec<AccountId> {
+            let rango = self.rango_usuarios(&estado);
+            Self::pagina_de_lista(&self.usuarios_por_posicion, |posicion| (estado.clone(), posicion), rango, desde, cantidad)
+        }
+        /// Saca al usuario de la lista que corresponde a su estado.
+        fn quitar_usuario_de_lista(&mut self, usuario: &Usuario) -> Result<()> {
+            let posicion = self.posicion_de_usuario.take(usuario.id).ok_or(Error::USUARIO_NO_ENCONTRADO)?;
+            let mut rango = self.rango_usuarios(&usuario.estado);
+            let estado = usuario.estado.clone();
+            Self::quitar_de_lista(&mut self.usuarios_por_posicion, |posicion| (estado.clone(), posicion), &mut rango, posicion)?;
+            self.rangos_usuarios.insert(&usuario.estado, &rango);
+            Ok(())
+        }
+
+        /// El propio usuario o una cuenta con alguno de los permisos indicados.
+        fn puede_leer_datos_de(&self, id_usuario: AccountId, permisos: &[PERMISO]) -> bool {
+            let caller = self.env().caller();
+            caller == id_usuario || permisos.iter().any(|permiso| self.cuenta_tiene_permiso(caller, permiso))
+        }
+
+        /// Los rechazos hechos sin indicar motivo quedan registrados como SIN_ESPECIFICAR.
+        fn motivo_si_rechaza(aceptar_usuario:bool) -> Option<MotivoRechazo> {
+            (!aceptar_usuario).then(MotivoRechazo::default)
+        }
+
+        /// Acepta o rechaza a un usuario pendiente. Con `None` lo acepta; con un motivo lo rechaza
+        /// y deja el rechazo registrado. Si devuelve un error no modifica nada.
+        fn procesar_usuario(&mut self, usuario_id: AccountId, motivo_rechazo:Option<MotivoRechazo>) -> Result<()> {
+            let mut usuario = self.obtener_usuario_pendiente_por_id(usuario_id).ok_or(Error::USUARIO_NO_PENDIENTE)?;
+
+            self.quitar_usuario_de_lista(&usuario)?;
+            let Some(motivo) = motivo_rechazo else {
+                usuario.estado = ESTADO_USUARIO::ACEPTADO;
+                self.agregar_usuario(usuario)?;
+                self.env().emit_event(UsuarioAprobado { usuario: usuario_id });
+                return Ok(());
+            };
+
+            usuario.estado = ESTADO_USUARIO::RECHAZADO;
+            self.agregar_usuario(usuario)?;
+            self.env().emit_event(UsuarioRechazado { usuario: usuario_id, codigo: motivo.codigo.clone() });
+            self.registrar_rechazo(usuario_id, None, None, motivo);
+            Ok(())
+        }
+
+        /// Vuelve a poner en la cola de pendientes a un usuario rechazado.
+        fn reincorporar_usuario(&mut self, usuario_id: AccountId) -> Result<()> {
+            let mut usuario = self.obtener_usuario_rechazado_por_id(usuario_id).ok_or(Error::USUARIO_NO_ENCONTRADO)?;
+            self.quitar_usuario_de_lista(&usuario)?;
+            usuario.estado = ESTADO_USUARIO::PENDIENTE;
+            self.agregar_usuario(usuario)
+        }
+
+        /// Agrega un rechazo al historial del usuario.
+        fn registrar_rechazo(&mut self, usuario: AccountId, eleccion_id:Option<u64>, tipo:Option<TIPO_DE_USUARIO>, motivo:MotivoRechazo) {
+            let mut historial = self.historial_rechazos.get(usuario).unwrap_or_default();
+            historial.push(Rechazo {
+                eleccion_id,
+                tipo,
+                motivo,
+                rechazado_por: self.env().caller(),
+                fecha: self.env().block_timestamp(),
+                apelacion: None,
+            });
+            self.historial_rechazos.insert(usuario, &historial);
+        }
+
+        /// Posición en el historial del último rechazo del usuario en el sistema (`None`) o en una elección.
+        fn ultimo_rechazo(historial: &[Rechazo], eleccion_id:Option<u64>) -> Option<usize> {
+            historial.iter().rposition(|rechazo| rechazo.eleccion_id == eleccion_id)
+        }
+
+        /// Permiso necesario para aceptar o rechazar usuarios en el sistema (`None`) o en una elección.
+        fn permiso_de_ambito(eleccion_id:Option<u64>) -> PERMISO {
+            match eleccion_id {
+                None => PERMISO::APROBAR_USUARIOS,
+                Some(eleccion_id) => PERMISO::GESTIONAR_ELECCION(eleccion_id),
+            }
+        }
+
+        fn validar_longitud_texto(texto:&str) -> Result<()> {
+            if texto.len() > LONGITUD_MAXIMA_TEXTO { return Err(Error::TEXTO_DEMASIADO_LARGO); }
+            Ok(())
+        }
+
+        /// Guarda el usuario y lo agrega al final de la lista que corresponde a su estado.
+        fn agregar_usuario(&mut self, usuario: Usuario) -> Result<()> {
+            let mut rango = self.rango_usuarios(&usuario.estado);
+            let posicion = rango.agregar()?;
+            self.usuarios_por_posicion.insert((usuario.estado.clone(), posicion), &usuario.id);
+            self.posicion_de_usuario.insert(usuario.id, &posicion);
+            self.rangos_usuarios.insert(&usuario.estado, &rango);
+
+            // El índice por DNI apunta a la primera cuenta que lo registró.
+            if !self.usuario_por_dni.contains(usuario.dni_hash) {
+                self.usuario_por_dni.insert(usuario.dni_hash, &usuario.id);
+            }
+            self.usuarios.insert(usuario.id, &usuario);
+            Ok(())
+        }
+
+        fn existe_eleccion(&self, eleccion_id:u64) -> bool
+        {
+            self.elecciones.contains(eleccion_id)
+        }
+
+        /// Devuelve la elección con su estado vigente, sin guardarlo. Es la que usan las consultas.
+        fn obtener_eleccion_por_id(&self, eleccion_id:u64) -> Option<Eleccion> {
+            let mut eleccion = self.elecciones.get(eleccion_id)?;
+            eleccion.estado = eleccion.estado_vigente(self.env().block_timestamp());
+            Some(eleccion)
+        }
+
+        /// Devuelve la elección con su estado vigente, guardando y emitiendo los cambios de estado pendientes.
+        /// Es la que usan las operaciones que modifican la elección.
+        fn actualizar_eleccion(&mut self, eleccion_id:u64) -> Result<Eleccion> {
+            let mut eleccion = self.elecciones.get(eleccion_id).ok_or(Error::ELECCION_NO_EXISTE)?;
+            if eleccion.actualizar_estado(self.env().block_timestamp())? {
+                self.guardar_eleccion(&eleccion);
+            }
+            Ok(eleccion)
+        }
+
+        fn guardar_eleccion(&mut self, eleccion: &Eleccion) {
+            self.elecciones.insert(eleccion.id, eleccion);
+        }
+
+        /// Convierte fechas con formato "dd-mm-aaaa hh:mm" a milisegundos. Las fechas anteriores a 1970 no son válidas.
+        fn convertir_fechas(fecha_inicial:&str, fecha_final:&str) -> Result<(u64, u64)> {
+            let fecha_inicial_milisegundos = chrono::NaiveDateTime::parse_from_str(fecha_inicial, "%d-%m-%Y %H:%M")
+                .map_err(|_| Error::FORMATO_FECHA_INICIAL_INVALIDO)?;
+            let fecha_final_milisegundos = chrono::NaiveDateTime::parse_from_str(fecha_final, "%d-%m-%Y %H:%M")
+                .map_err(|_| Error::FORMATO_FECHA_FINAL_INVALIDO)?;
+            Ok((
+                u64::try_from(fecha_inicial_milisegundos.and_utc().timestamp_millis()).map_err(|_| Error::FORMATO_FECHA_INICIAL_INVALIDO)?,
+                u64::try_from(fecha_final_milisegundos.and_utc().timestamp_millis()).map_err(|_| Error::FORMATO_FECHA_FINAL_INVALIDO)?,
+            ))
+        }
+
+        /// Crea y guarda una elección CERRADA con el siguiente id.
+        fn nueva_eleccion(&mut self, fecha_inicio:u64, fecha_final:u64, configuracion:ConfiguracionEleccion, eleccion_anterior:Option<u64>) -> Result<Eleccion> {
+            let eleccion_id = self.cantidad_elecciones.checked_add(1).ok_or(Error::OVERFLOW)?;
+            let eleccion = Eleccion {
+                id: eleccion_id,
+                estado: ESTADO_ELECCION::CERRADA,
+                fecha_inicio,
+                fecha_final,
+                cantidad_candidatos: 0,
+                candidatos_retirados: 0,
+                cantidad_votantes: 0,
+                votos_emitidos: 0,
+                configuracion,
+                compromisos_emitidos: 0,
+                compromisos_revelados: 0,
+                cantidad_boletas: 0,
+                votos_en_blanco: 0,
+                votos_nulos: 0,
+                cantidad_listas: 0,
+                escrutada: false,
+                ganador: None,
+                eleccion_anterior,
+                segunda_vuelta: None,
+            };
+            self.guardar_eleccion(&eleccion);
+            self.cantidad_elecciones = eleccion_id;
+            Ok(eleccion)
+        }
+
+        /// Valida la configuración y crea la elección con las fechas indicadas. Quien llama ya verificó el permiso.
+        fn registrar_eleccion(&mut self, fecha_inicial:&str, fecha_final:&str, configuracion:ConfiguracionEleccion) -> Result<u64>
+        {
+            if self.migracion_en_curso { return Err(Error::MIGRACION_EN_CURSO); }
+            let sistema_mayoria_simple = configuracion.sistema_escrutinio == SISTEMA_ESCRUTINIO::MAYORIA_SIMPLE;
+            if configuracion.modo_votacion == MODO_VOTACION::SECRETO
+                && (configuracion.duracion_revelacion == 0 || !sistema_mayoria_simple) {
+                return Err(Error::CONFIGURACION_INVALIDA);
+            }
+            if configuracion.balotaje && !sistema_mayoria_simple { return Err(Error::CONFIGURACION_INVALIDA); }
+            if let SISTEMA_ESCRUTINIO::DHONDT { bancas, umbral } = configuracion.sistema_escrutinio {
+                if bancas == 0 || umbral > 10_000 { return Err(Error::CONFIGURACION_INVALIDA); }
+            }
+            if configuracion.sistema_escrutinio == (SISTEMA_ESCRUTINIO::APROBACION { maximo_aprobaciones: Some(0) }) {
+                return Err(Error::CONFIGURACION_INVALIDA);
+            }
+            if configuracion.permite_delegacion
+                && (configuracion.modo_votacion == MODO_VOTACION::SECRETO || !sistema_mayoria_simple || !configuracion.ponderacion_claustros.is_empty()) {
+                return Err(Error::CONFIGURACION_INVALIDA);
+            }
+            let pesos = &configuracion.ponderacion_claustros;
+            if !pesos.is_empty() && (!sistema_mayoria_simple || configuracion.balotaje) { return Err(Error::CONFIGURACION_INVALIDA); }
+            for (posicion, ponderacion) in pesos.iter().enumerate() {
+                if ponderacion.peso == 0 || pesos[..posicion].iter().any(|otra| otra.claustro == ponderacion.claustro) {
+                    return Err(Error::CONFIGURACION_INVALIDA);
+                }
+            }
+
+            let (fecha_inicio, fecha_final) = Self::convertir_fechas(fecha_inicial, fecha_final)?;
+            let eleccion = self.nueva_eleccion(fecha_inicio, fecha_final, configuracion, None)?;
+            self.env().emit_event(EleccionCreada {
+                eleccion_id: eleccion.id,
+                fecha_inicio: eleccion.fecha_inicio,
+                fecha_final: eleccion.fecha_final,
+            });
+
+            Ok(eleccion.id)
+        }
+
+        fn validar_estado_eleccion_para_inscripciones(&mut self, eleccion_id:u64) -> Result<Eleccion>{
+            let eleccion = self.actualizar_eleccion(eleccion_id)?;
+            match eleccion.estado {
+                ESTADO_ELECCION::ABIERTA => Ok(eleccion),
+                ESTADO_ELECCION::CERRADA => Err(Error::ELECCION_NO_ABIERTA),
+                ESTADO_ELECCION::INICIADA => Err(Error::VOTACION_INICIADA),
+                ESTADO_ELECCION::FINALIZADA => Err(Error::ELECCION_FINALIZADA),
+            }
+        }
+        /// Las bajas del padrón y de candidatos y los cambios en la descripción de la elección o en las plataformas
+        /// de los candidatos solo se permiten antes de que empiece la votación.
+        fn validar_eleccion_sin_iniciar(&mut self, eleccion_id:u64) -> Result<Eleccion>{
+            let eleccion = self.actualizar_eleccion(eleccion_id)?;
+            eleccion.validar_sin_iniciar()?;
+            Ok(eleccion)
+        }
+        fn validar_estado_eleccion_para_votaciones(&mut self, eleccion_id:u64) -> Result<Eleccion>{
+            let eleccion = self.actualizar_eleccion(eleccion_id)?;
+            if !eleccion.tiene_fechas() {
+                return Err(Error::FECHAS_NO_DEFINIDAS);
+            }
+            match eleccion.estado {
+                ESTADO_ELECCION::INICIADA => Ok(eleccion),
+                ESTADO_ELECCION::FINALIZADA => Err(Error::ELECCION_FINALIZADA),
+                _ => Err(Error::VOTACION_NO_INICIADA),
+            }
+        }
+
+        fn estado_inscripcion(&self, eleccion_id:u64, id: AccountId) -> Option<ESTADO_INSCRIPCION> {
+            self.inscripciones.get((eleccion_id, id))
+        }
+        fn es_votante(&self, eleccion_id:u64, id: AccountId) -> bool {
+            self.estado_inscripcion(eleccion_id, id) == Some(ESTADO_INSCRIPCION::VOTANTE)
+        }
+        fn es_candidato(&self, eleccion_id:u64, id: AccountId) -> bool {
+            self.estado_inscripcion(eleccion_id, id) == Some(ESTADO_INSCRIPCION::CANDIDATO)
+        }
+        fn es_usuario_pendiente_en_eleccion(&self, eleccion_id:u64, id: AccountId) -> bool {
+            self.estado_inscripcion(eleccion_id, id) == Some(ESTADO_INSCRIPCION::PENDIENTE)
+        }
+        fn es_usuario_rechazado_en_eleccion(&self, eleccion_id:u64, id: AccountId) -> bool {
+            self.estado_inscripcion(eleccion_id, id) == Some(ESTADO_INSCRIPCION::RECHAZADA)
+        }
+
+        /// Devuelve el candidato con ese id, o None si no existe o se retiró.
+        fn obtener_informacion_candidato_en_eleccion(&self, eleccion: &Eleccion, candidato_id:u64) -> Option<CandidatoConteo>
+        {
+            if !eleccion.existe_candidato(candidato_id) { return None; }
+            self.candidatos.get((eleccion.id, candidato_id))
+        }
+
+        /// Votantes del padrón desde la posición `desde`, hasta `cantidad` y nunca más de MAXIMO_POR_PAGINA.
+        fn pagina_de_padron(&self, eleccion: &Eleccion, desde:u64, cantidad:u64) -> Vec<Votante> {
+            rango_de_pagina(0, eleccion.cantidad_votantes, desde, cantidad)
+                .filter_map(|indice| self.votantes_por_indice.get((eleccion.id, indice)))
+                .filter_map(|votante_id| self.votantes.get((eleccion.id, votante_id)))
+                .collect()
+        }
+        /// Candidatos vigentes de la elección ordenados por id. Los retirados no se incluyen.
+        fn obtener_candidatos_de_eleccion(&self, eleccion: &Eleccion) -> Vec<CandidatoConteo> {
+            (1..=eleccion.cantidad_candidatos)
+                .filter_map(|candidato_id| self.candidatos.get((eleccion.id, candidato_id)))
+                .collect()
+        }
+
+        /// Agrega un votante al padrón de la elección.
+        fn agregar_votante(&mut self, eleccion: &mut Eleccion, votante: Votante) -> Result<()> {
+            if let Some(claustro) = &votante.claustro {
+                let votantes = self.validar_limite_ponderacion(eleccion, claustro)?;
+                self.votantes_por_claustro.insert((eleccion.id, claustro.clone()), &votantes);
+            }
+            let indice = eleccion.cantidad_votantes;
+            eleccion.cantidad_votantes = indice.checked_add(1).ok_or(Error::OVERFLOW)?;
+            self.votantes_por_indice.insert((eleccion.id, indice), &votante.usuario_id);
+            self.indice_de_votante.insert((eleccion.id, votante.usuario_id), &indice);
+            self.inscripciones.insert((eleccion.id, votante.usuario_id), &ESTADO_INSCRIPCION::VOTANTE);
+            self.votantes.insert((eleccion.id, votante.usuario_id), &votante);
+            Ok(())
+        }
+        /// Valida que el escrutinio ponderado se pueda calcular con un votante más en `claustro` y devuelve
+        /// los votantes que tendría el claustro. Los votos válidos de cada claustro no superan a sus votantes,
+        /// así que el mínimo común múltiplo que usa `escrutar_ponderado` no supera el producto de los votantes
+        /// de los claustros, y sus fracciones y porcentajes no superan suma(pesos) * 10.000 * ese producto.
+        /// Validarlo al aceptar votantes evita que la elección quede sin poder escrutarse.
+        fn validar_limite_ponderacion(&self, eleccion: &Eleccion, claustro:&CLAUSTRO) -> Result<u64> {
+            let votantes_del_claustro = self.votantes_por_claustro.get((eleccion.id, claustro.clone())).unwrap_or(0)
+                .checked_add(1).ok_or(Error::OVERFLOW)?;
+            let mut limite:u128 = 10_000;
+            let mut suma_pesos:u128 = 0;
+            for ponderacion in eleccion.configuracion.ponderacion_claustros.iter() {
+                let votantes = if ponderacion.claustro == *claustro { votantes_del_claustro } else {
+                    self.votantes_por_claustro.get((eleccion.id, ponderacion.claustro.clone())).unwrap_or(0)
+                };
+                limite = limite.checked_mul(votantes.max(1) as u128).ok_or(Error::LIMITE_PONDERACION_EXCEDIDO)?;
+                suma_pesos = suma_pesos.checked_add(ponderacion.peso as u128).ok_or(Error::LIMITE_PONDERACION_EXCEDIDO)?;
+            }
+            limite.checked_mul(suma_pesos).ok_or(Error::LIMITE_PONDERACION_EXCEDIDO)?;
+            Ok(votantes_del_claustro)
+        }
+
+        /// Quita un votante del padrón. El último votante del padrón pasa a ocupar su posición.
+        fn quitar_votante(&mut self, eleccion: &mut Eleccion, usuario_id: AccountId) -> Result<()> {
+            let indice = self.indice_de_votante.get((eleccion.id, usuario_id)).ok_or(Error::NO_ES_VOTANTE)?;
+            let ultimo = eleccion.cantidad_votantes.checked_sub(1).ok_or(Error::OVERFLOW)?;
+            if indice != ultimo {
+                let movido = self.votantes_por_indice.get((eleccion.id, ultimo)).ok_or(Error::NO_ES_VOTANTE)?;
+                self.votantes_por_indice.insert((eleccion.id, indice), &movido);
+                self.indice_de_votante.insert((eleccion.id, movido), &indice);
+            }
+            self.votantes_por_indice.remove((eleccion.id, ultimo));
+            eleccion.cantidad_votantes = ultimo;
+            if let Some(claustro) = self.votantes.get((eleccion.id, usuario_id)).and_then(|votante| votante.claustro) {
+                let votantes = self.votantes_por_claustro.get((eleccion.id, claustro.clone())).unwrap_or(0);
+                self.votantes_por_claustro.insert((eleccion.id, claustro), &votantes.saturating_sub(1));
+            }
+            self.indice_de_votante.remove((eleccion.id, usuario_id));
+            self.votantes.remove((eleccion.id, usuario_id));
+            self.inscripciones.remove((eleccion.id, usuario_id));
+            Ok(())
+        }
+        /// Quita un candidato de la elección. Su id no se reutiliza. Falla si integra una lista.
+        fn quitar_candidato(&mut self, eleccion: &mut Eleccion, usuario_id: AccountId) -> Result<u64> {
+            let candidato_id = self.candidato_por_usuario.get((eleccion.id, usuario_id)).ok_or(Error::NO_ES_CANDIDATO)?;
+            if self.lista_de_candidato.contains((eleccion.id, candidato_id)) { return Err(Error::CANDIDATO_EN_LISTA); }
+            eleccion.candidatos_retirados = eleccion.candidatos_retirados.checked_add(1).ok_or(Error::OVERFLOW)?;
+            self.candidatos.remove((eleccion.id, candidato_id));
+            self.plataformas.remove((eleccion.id, candidato_id));
+            self.candidato_por_usuario.remove((eleccion.id, usuario_id));
+            self.inscripciones.remove((eleccion.id, usuario_id));
+            Ok(candidato_id)
+        }
+
+        /// Agrega un candidato a la elección asignándole el siguiente id.
+        fn agregar_candidato(&mut self, eleccion: &mut Eleccion, usuario_id: AccountId, votos_totales: u64) -> Result<u64> {
+            let candidato_id = eleccion.cantidad_candidatos.checked_add(1).ok_or(Error::OVERFLOW)?;
+            eleccion.cantidad_candidatos = candidato_id;
+            self.candidatos.insert((eleccion.id, candidato_id), &CandidatoConteo {
+                id: candidato_id,
+                usuario_id,
+                votos_totales,
+            });
+            self.candidato_por_usuario.insert((eleccion.id, usuario_id), &candidato_id);
+            self.inscripciones.insert((eleccion.id, usuario_id), &ESTADO_INSCRIPCION::CANDIDATO);
+            Ok(candidato_id)
+        }
+
+        /// Valida que quien llama pueda votar en la elección y devuelve la elección junto con su id.
+        fn validar_votante(&mut self, eleccion_id:u64) -> Result<(Eleccion, AccountId)>
+        {
+            // es usuario valido en el sistema (no esta pendiente de aprobacion y no esta rechazado)
+            let id = self.env().caller();
+            if !self.es_usuario_registrado(id) { return Err(Error::USUARIO_NO_REGISTRADO); }
+
+            // el estado de la eleccion es INICIADA
+            let eleccion = self.validar_estado_eleccion_para_votaciones(eleccion_id)?;
+
+            //Validar que un usuario que ya ha sido rechazado en la misma eleccion no intente volver a ponerse como pendiente
+            if self.es_usuario_rechazado_en_eleccion(eleccion_id, id) {  return Err(Error::INSCRIPCION_RECHAZADA); }
+            if self.es_usuario_pendiente_en_eleccion(eleccion_id, id) { return Err(Error::INSCRIPCION_PENDIENTE); }
+            if self.es_candidato(eleccion_id, id) {return Err(Error::YA_ES_CANDIDATO);}
+            if !self.es_votante(eleccion_id, id) { return Err(Error::NO_ES_VOTANTE);}
+            if self.votantes.get((eleccion_id, id)).is_some_and(|votante| votante.voto_emitido) { return Err(Error::YA_VOTO); }
+            Ok((eleccion, id))
+        }
+
+        /// Claustro con el que el usuario entra al padrón de la elección.
+        /// En una elección ponderada el usuario debe tener asignado un claustro con peso en la elección.
+        fn validar_claustro(&self, eleccion: &Eleccion, usuario:AccountId) -> Result<Option<CLAUSTRO>>
+        {
+            if !eleccion.es_ponderada() { return Ok(None); }
+            let claustro = self.claustros.get(usuario).ok_or(Error::SIN_CLAUSTRO)?;
+            if eleccion.peso_claustro(&claustro).is_none() { return Err(Error::CLAUSTRO_NO_PONDERADO); }
+            Ok(Some(claustro))
+        }
+
+        /// Suma el voto al conteo del claustro del votante, si la elección es ponderada.
+        /// `opcion` es el id del candidato o VOTO_EN_BLANCO.
+        fn sumar_voto_a_claustro(&mut self, eleccion: &Eleccion, votante_id:AccountId, opcion:u64) -> Result<()>
+        {
+            if !eleccion.es_ponderada() { return Ok(()); }
+            let claustro = self.votantes.get((eleccion.id, votante_id))
+                .and_then(|votante| votante.claustro)
+                .ok_or(Error::SIN_CLAUSTRO)?;
+            let votos = self.votos_por_claustro.get((eleccion.id, opcion, claustro.clone())).unwrap_or(0);
+            self.votos_por_claustro.insert((eleccion.id, opcion, claustro), &votos.checked_add(1).ok_or(Error::OVERFLOW)?);
+            Ok(())
+        }
+
+        /// Marca al votante como que ya emitió su voto y lo suma a los votos emitidos de la elección.
+        fn registrar_voto_emitido(&mut self, eleccion: &mut Eleccion, votante_id:AccountId) -> Result<()>
+        {
+            let mut votante = self.votantes.get((eleccion.id, votante_id)).ok_or(Error::NO_ES_VOTANTE)?;
+            if votante.voto_emitido { return Err(Error::YA_VOTO); }
+            eleccion.votos_emitidos = eleccion.votos_emitidos.checked_add(1).ok_or(Error::OVERFLOW)?;
+            votante.voto_emitido = true;
+            self.votantes.insert((eleccion.id, votante_id), &votante);
+            self.env().emit_event(VotoEmitido { eleccion_id: eleccion.id, votante: votante_id });
+            Ok(())
+        }
+
+        /// Registra el voto del propio votante, que deja sin efecto su delegación si la tenía, junto con los votos
+        /// de quienes delegaron en él directa o indirectamente. Devuelve cuántos votos representa, incluido el propio.
+        fn registrar_voto_con_delegaciones(&mut self, eleccion: &mut Eleccion, votante_id:AccountId) -> Result<u64>
+        {
+            self.registrar_voto_emitido(eleccion, votante_id)?;
+            if let Some(delegado) = self.anular_delegacion(eleccion.id, votante_id) {
+                self.env().emit_event(DelegacionRevocada { eleccion_id: eleccion.id, votante: votante_id, delegado });
+            }
+            let mut pendientes = self.delegantes.get((eleccion.id, votante_id)).unwrap_or_default();
+            let mut votos_delegados:u64 = 0;
+            while let Some(delegante) = pendientes.pop() {
+                pendientes.extend(self.delegantes.get((eleccion.id, delegante)).unwrap_or_default());
+                self.registrar_voto_emitido(eleccion, delegante)?;
+                votos_delegados = votos_delegados.checked_add(1).ok_or(Error::OVERFLOW)?;
+            }
+            if votos_delegados > 0 {
+                self.votos_delegados.insert((eleccion.id, votante_id), &votos_delegados);
+            }
+            votos_delegados.checked_add(1).ok_or(Error::OVERFLOW)
+        }
+
+        /// Quita la delegación del votante, si la tenía, y devuelve en quién había delegado.
+        fn anular_delegacion(&mut self, eleccion_id:u64, votante_id:AccountId) -> Option<AccountId>
+        {
+            let delegado = self.delegaciones.take((eleccion_id, votante_id))?;
+            let mut delegantes = self.delegantes.get((eleccion_id, delegado)).unwrap_or_default();
+            delegantes.retain(|delegante| *delegante != votante_id);
+            self.delegantes.insert((eleccion_id, delegado), &delegantes);
+            Some(delegado)
+        }
+
+        fn sumar_voto_a_candidato(&mut self, eleccion: &Eleccion, candidato_id:u64, votos:u64) -> Result<()>
+        {
+            let mut candidato = self.obtener_informacion_candidato_en_eleccion(eleccion, candidato_id)
+                .ok_or(Error::CANDIDATO_NO_EXISTE)?;
+            candidato.votos_totales = candidato.votos_totales.checked_add(votos).ok_or(Error::OVERFLOW)?;
+            self.candidatos.insert((eleccion.id, candidato_id), &candidato);
+            Ok(())
+        }
+
+        fn votar_candidato(&mut self, eleccion: &mut Eleccion, votante_id:AccountId, candidato_id:u64) -> Result<()>
+        {
+            if eleccion.es_secreta() || !eleccion.es_mayoria_simple() { return Err(Error::MODO_VOTACION_INCORRECTO); }
+            if self.obtener_informacion_candidato_en_eleccion(eleccion, candidato_id).is_none() { return Err(Error::CANDIDATO_NO_EXISTE); }
+            self.sumar_voto_a_claustro(eleccion, votante_id, candidato_id)?;
+            let votos = self.registrar_voto_con_delegaciones(eleccion, votante_id)?;
+            self.sumar_voto_a_candidato(eleccion, candidato_id, votos)
+        }
+
+        /// Una boleta preferencial es válida si no está vacía, todos sus candidatos existen y ninguno se repite.
+        fn validar_boleta_preferencial(&self, eleccion: &Eleccion, preferencias: &[u64]) -> Result<()>
+        {
+            if preferencias.is_empty() { return Err(Error::BOLETA_VACIA); }
+            for (posicion, candidato_id) in preferencias.iter().enumerate() {
+                if self.obtener_informacion_candidato_en_eleccion(eleccion, *candidato_id).is_none() { return Err(Error::CANDIDATO_NO_EXISTE); }
+                if preferencias[..posicion].contains(candidato_id) { return Err(Error::CANDIDATO_REPETIDO); }
+            }
+            Ok(())
+        }
+
+        /// Realiza el escrutinio de la elección si todavía no se hizo y el conteo ya es definitivo, leyendo hasta
+        /// `boletas` boletas preferenciales. Devuelve si la elección quedó escrutada.
+        /// En MAYORIA_SIMPLE los votos ya están contados, solo se marca la elección como escrutada.
+        fn escrutar(&mut self, eleccion: &mut Eleccion, boletas:u64) -> Result<bool>
+        {
+            if eleccion.escrutada { return Ok(true); }
+            if !eleccion.conteo_disponible(self.env().block_timestamp()) { return Ok(false); }
+            match eleccion.configuracion.sistema_escrutinio {
+                SISTEMA_ESCRUTINIO::MAYORIA_SIMPLE if eleccion.es_ponderada() => self.escrutar_ponderado(eleccion)?,
+                SISTEMA_ESCRUTINIO::MAYORIA_SIMPLE => self.escrutar_mayoria_simple(eleccion)?,
+                SISTEMA_ESCRUTINIO::PREFERENCIAL => {
+                    if !self.escrutar_preferencial(eleccion, boletas)? { return Ok(false); }
+                },
+                SISTEMA_ESCRUTINIO::DHONDT { bancas, umbral } => {
+                    let reparto = self.repartir_bancas(eleccion, bancas, umbral)?;
+                    self.reparto_bancas.insert(eleccion.id, &reparto);
+                },
+                SISTEMA_ESCRUTINIO::APROBACION { .. } => {
+                    let resultados = self.escrutar_aprobacion(eleccion)?;
+                    let aprobaciones:Vec<(u64, u128)> = resultados.iter()
+                        .map(|resultado| (resultado.candidato_id, resultado.aprobaciones as u128))
+                        .collect();
+                    eleccion.ganador = Self::unico_maximo(&aprobaciones);
+                    self.resultados_aprobacion.insert(eleccion.id, &resultados);
+                },
+                SISTEMA_ESCRUTINIO::SCHULZE => {
+                    let (caminos, ganador) = self.escrutar_schulze(eleccion)?;
+                    eleccion.ganador = ganador;
+                    self.caminos_mas_fuertes.insert(eleccion.id, &caminos);
+                },
+                SISTEMA_ESCRUTINIO::REFERENDUM => {
+                    let resultado = self.escrutar_referendum(eleccion)?;
+                    self.env().emit_event(ReferendumEscrutado { eleccion_id: eleccion.id, aprobado: resultado.aprobado });
+                    self.resultados_referendum.insert(eleccion.id, &resultado);
+                },
+            }
+            eleccion.escrutada = true;
+            self.guardar_eleccion(eleccion);
+            self.env().emit_event(EleccionEscrutada { eleccion_id: eleccion.id, ganador: eleccion.ganador });
+            Ok(true)
+        }
+
+        /// Gana el más votado; si hay empate en el primer puesto no hay ganador.
+        /// Con balotaje, si el más votado no alcanza lo necesario se crea la segunda vuelta entre los dos primeros.
+        /// Un empate en el segundo puesto lo desempata el menor id de candidato.
+        fn escrutar_mayoria_simple(&mut self, eleccion: &mut Eleccion) -> Result<()>
+        {
+            let mut candidatos = self.obtener_candidatos_de_eleccion(eleccion);
+            candidatos.sort_by(|a, b| b.votos_totales.cmp(&a.votos_totales).then(a.id.cmp(&b.id)));
+            let mut votos_validos:u64 = 0;
+            for candidato in candidatos.iter() {
+                votos_validos = votos_validos.checked_add(candidato.votos_totales).ok_or(Error::OVERFLOW)?;
+            }
+            let Some(primero) = candidatos.first() else { return Ok(()); };
+            let segundo = candidatos.get(1);
+            if votos_validos == 0 { return Ok(()); }
+            let base = eleccion.base_porcentual(votos_validos)?;
+            let votos_segundo = segundo.map_or(0, |candidato| candidato.votos_totales);
+
+            if !eleccion.configuracion.balotaje {
+                if primero.votos_totales > votos_segundo { eleccion.ganador = Some(primero.id); }
+                return Ok(());
+            }
+            match segundo {
+                Some(segundo) if !Self::gana_en_primera_vuelta(primero.votos_totales, votos_segundo, base)? => {
+                    let finalistas = [primero.usuario_id, segundo.usuario_id];
+                    self.crear_segunda_vuelta(eleccion, &finalistas)
+                },
+                _ => {
+                    eleccion.ganador = Some(primero.id);
+                    Ok(())
+                }
+            }
+        }
+
+        /// Votos de cada claustro de una elección ponderada, en el orden de su configuración.
+        fn conteo_por_claustro(&self, eleccion: &Eleccion) -> Result<Vec<ConteoClaustro>>
+        {
+            let candidatos = self.obtener_candidatos_de_eleccion(eleccion);
+            let mut conteos = Vec::new();
+            for ponderacion in eleccion.configuracion.ponderacion_claustros.iter() {
+                let votos_de = |opcion:u64| self.votos_por_claustro.get((eleccion.id, opcion, ponderacion.claustro.clone())).unwrap_or(0);
+                let votos:Vec<ConteoRonda> = candidatos.iter()
+                    .map(|candidato| ConteoRonda { candidato_id: candidato.id, votos: votos_de(candidato.id) })
+                    .collect();
+                let votos_en_blanco = votos_de(VOTO_EN_BLANCO);
+                let mut votos_validos = if eleccion.configuracion.blancos_en_base { votos_en_blanco } else { 0 };
+                for conteo in votos.iter() {
+                    votos_validos = votos_validos.checked_add(conteo.votos).ok_or(Error::OVERFLOW)?;
+                }
+                conteos.push(ConteoClaustro { claustro: ponderacion.claustro.clone(), peso: ponderacion.peso, votos, votos_en_blanco, votos_validos });
+            }
+            Ok(conteos)
+        }
+
+        /// Escrutinio ponderado por claustros. Cada claustro aporta su peso repartido en proporción a los votos
+        /// válidos que emitió, de modo que un candidato obtiene suma(peso * votos / votos_validos) / suma(pesos).
+        /// Los claustros sin votos válidos no participan y su peso no integra la suma.
+        /// Para no redondear, todas las fracciones se expresan sobre el mínimo común múltiplo de los votos válidos
+        /// de los claustros, y solo el porcentaje informado se trunca. Gana el candidato con mayor fracción;
+        /// si hay empate en el primer puesto, o nadie obtuvo votos, no hay ganador.
+        /// `validar_limite_ponderacion` garantiza al armar el padrón que estas cuentas no desbordan.
+        fn escrutar_ponderado(&mut self, eleccion: &mut Eleccion) -> Result<()>
+        {
+            let conteos:Vec<ConteoClaustro> = self.conteo_por_claustro(eleccion)?.into_iter()
+                .filter(|conteo| conteo.votos_validos > 0)
+                .collect();
+            let mut minimo_comun:u128 = 1;
+            let mut suma_pesos:u128 = 0;
+            for conteo in conteos.iter() {
+                minimo_comun = Self::mcm(minimo_comun, conteo.votos_validos as u128)?;
+                suma_pesos = suma_pesos.checked_add(conteo.peso as u128).ok_or(Error::OVERFLOW)?;
+            }
+            let denominador = suma_pesos.checked_mul(minimo_comun).ok_or(Error::OVERFLOW)?;
+
+            let mut resultados = Vec::new();
+            for candidato in self.obtener_candidatos_de_eleccion(eleccion) {
+                let mut numerador:u128 = 0;
+                for conteo in conteos.iter() {
+                    let votos = conteo.votos.iter().find(|votos| votos.candidato_id == candidato.id).map_or(0, |votos| votos.votos);
+                    let factor = minimo_comun.checked_div(conteo.votos_validos as u128).ok_or(Error::OVERFLOW)?;
+                    let termino = (conteo.peso as u128).checked_mul(votos as u128)
+                        .and_then(|producto| producto.checked_mul(factor))
+                        .ok_or(Error::OVERFLOW)?;
+                    numerador = numerador.checked_add(termino).ok_or(Error::OVERFLOW)?;
+                }
+                resultados.push(ResultadoPonderado {
+                    candidato_id: candidato.id,
+                    numerador,
+                    denominador,
+                    porcentaje: Self::porcentaje(numerador, denominador)?,
+                });
+            }
+
+            let numeradores:Vec<(u64, u128)> = resultados.iter()
+                .map(|resultado| (resultado.candidato_id, resultado.numerador))
+                .collect();
+            eleccion.ganador = Self::unico_maximo(&numeradores);
+            self.resultados_ponderados.insert(eleccion.id, &resultados);
+            Ok(())
+        }
+
+        /// Cada candidato obtiene tantos votos como boletas lo aprobaron. Los porcentajes son sobre la base porcentual
+        /// de las boletas emitidas, no sobre el total de aprobaciones.
+        fn escrutar_aprobacion(&self, eleccion: &Eleccion) -> Result<Vec<ResultadoAprobacion>>
+        {
+            let base = eleccion.base_porcentual(eleccion.cantidad_boletas)?;
+            self.obtener_candidatos_de_eleccion(eleccion).into_iter()
+                .map(|candidato| Ok(ResultadoAprobacion {
+                    candidato_id: candidato.id,
+                    aprobaciones: candidato.votos_totales,
+                    porcentaje: Self::porcentaje(candidato.votos_totales as u128, base as u128)?,
+                }))
+                .collect()
+        }
+
+        /// Posición del candidato en las matrices de preferencias por pares.
+        fn indice_candidato(candidato_id:u64) -> Result<usize>
+        {
+            let indice = candidato_id.checked_sub(1).ok_or(Error::CANDIDATO_NO_EXISTE)?;
+            usize::try_from(indice).map_err(|_| Error::OVERFLOW)
+        }
+
+        /// Matriz de preferencias por pares de la elección, en cero si todavía no recibió boletas.
+        /// Siempre tiene una fila y una columna por cada id de candidato asignado, aunque la matriz guardada
+        /// sea de antes de que se sumara algún candidato.
+        fn obtener_matriz_pares(&self, eleccion: &Eleccion) -> MatrizPares
+        {
+            let candidatos = eleccion.cantidad_candidatos as usize;
+            let mut matriz = self.matrices_pares.get(eleccion.id).unwrap_or_default();
+            for fila in matriz.iter_mut() { fila.resize(candidatos, 0); }
+            matriz.resize(candidatos, ink::prelude::vec![0; candidatos]);
+            matriz
+        }
+
+        /// Suma una boleta a la matriz de preferencias por pares. Cada candidato de la boleta es preferido a los
+        /// que aparecen después y a los candidatos vigentes que no aparecen. Entre los que no aparecen no hay preferencia.
+        fn sumar_boleta_a_matriz(&mut self, eleccion: &Eleccion, preferencias: &[u64]) -> Result<()>
+        {
+            let mut matriz = self.obtener_matriz_pares(eleccion);
+            let sin_ordenar:Vec<u64> = self.obtener_candidatos_de_eleccion(eleccion).iter()
+                .map(|candidato| candidato.id)
+                .filter(|candidato_id| !preferencias.contains(candidato_id))
+                .collect();
+            let mut restantes = preferencias;
+            while let Some((preferido, siguientes)) = restantes.split_first() {
+                let fila = Self::indice_candidato(*preferido)?;
+                for otro in siguientes.iter().chain(sin_ordenar.iter()) {
+                    let columna = Self::indice_candidato(*otro)?;
+                    matriz[fila][columna] = matriz[fila][columna].checked_add(1).ok_or(Error::OVERFLOW)?;
+                }
+                restantes = siguientes;
+            }
+            self.matrices_pares.insert(eleccion.id, &matriz);
+            Ok(())
+        }
+
+        /// Método de Schulze. Un par (a, b) tiene un enlace de fuerza d[a][b] si más votantes prefieren a sobre b
+        /// que al revés. La fuerza de un camino es la de su enlace más débil, y el camino más fuerte entre dos candidatos
+        /// se calcula con una variante de Floyd-Warshall. Gana el candidato cuyo camino más fuerte hacia cada rival es
+        /// al menos tan fuerte como el del rival hacia él; si más de uno cumple esa condición, o no hubo boletas,
+        /// no hay ganador. Devuelve la matriz de caminos más fuertes y el ganador.
+        fn escrutar_schulze(&self, eleccion: &Eleccion) -> Result<(MatrizPares, Option<u64>)>
+        {
+            let matriz = self.obtener_matriz_pares(eleccion);
+            let mut caminos:MatrizPares = matriz.iter().map(|fila| ink::prelude::vec![0; fila.len()]).collect();
+            let mut en_carrera = Vec::new();
+            for candidato in self.obtener_candidatos_de_eleccion(eleccion) {
+                en_carrera.push((candidato.id, Self::indice_candidato(candidato.id)?));
+            }
+
+            for (_, a) in en_carrera.iter() {
+                for (_, b) in en_carrera.iter() {
+                    if matriz[*a][*b] > matriz[*b][*a] { caminos[*a][*b] = matriz[*a][*b]; }
+                }
+            }
+            for (_, intermedio) in en_carrera.iter() {
+                for (_, a) in en_carrera.iter() {
+                    if a == intermedio { continue; }
+                    for (_, b) in en_carrera.iter() {
+                        if b == a || b == intermedio { continue; }
+                        let por_intermedio = caminos[*a][*intermedio].min(caminos[*intermedio][*b]);
+                        if por_intermedio > caminos[*a][*b] { caminos[*a][*b] = por_intermedio; }
+                    }
+                }
+            }
+
+            if eleccion.cantidad_boletas == 0 { return Ok((caminos, None)); }
+            let mut ganadores = en_carrera.iter()
+                .filter(|(_, a)| en_carrera.iter().all(|(_, b)| caminos[*a][*b] >= caminos[*b][*a]));
+            let ganador = match (ganadores.next(), ganadores.next()) {
+                (Some((candidato_id, _)), None) => Some(*candidato_id),
+                _ => None,
+            };
+            Ok((caminos, ganador))
+        }
+
+        /// Suma los votos de cada opción según su sentido y decide si la propuesta alcanzó la mayoría requerida.
+        fn escrutar_referendum(&self, eleccion: &Eleccion) -> Result<ResultadoReferendum>
+        {
+            let propuesta = self.propuestas.get(eleccion.id).ok_or(Error::ELECCION_NO_EXISTE)?;
+            let mut votos = Vec::new();
+            let (mut votos_a_favor, mut votos_en_contra, mut abstenciones) = (0u64, 0u64, 0u64);
+            for (opcion_id, opcion) in (1u64..).zip(propuesta.opciones.iter()) {
+                let votos_opcion = self.votos_por_opcion.get((eleccion.id, opcion_id)).unwrap_or(0);
+                let acumulado = match opcion.sentido {
+                    SENTIDO_OPCION::A_FAVOR => &mut votos_a_favor,
+                    SENTIDO_OPCION::EN_CONTRA => &mut votos_en_contra,
+                    SENTIDO_OPCION::ABSTENCION => &mut abstenciones,
+                };
+                *acumulado = acumulado.checked_add(votos_opcion).ok_or(Error::OVERFLOW)?;
+                votos.push(ConteoOpcion { opcion_id, votos: votos_opcion });
+            }
+            let base = eleccion.base_porcentual(votos_a_favor.checked_add(votos_en_contra).ok_or(Error::OVERFLOW)?)?;
+            Ok(ResultadoReferendum {
+                votos,
+                votos_a_favor,
+                votos_en_contra,
+                abstenciones,
+                base,
+                aprobado: propuesta.mayoria.alcanzada(votos_a_favor, base)?,
+            })
+        }
+
+        /// Id con el mayor puntaje, si es mayor a cero y ningún otro lo iguala.
+        fn unico_maximo(puntajes: &[(u64, u128)]) -> Option<u64>
+        {
+            let maximo = puntajes.iter().map(|(_, puntaje)| *puntaje).max().filter(|maximo| *maximo > 0)?;
+            let mut con_maximo = puntajes.iter().filter(|(_, puntaje)| *puntaje == maximo);
+            let (id, _) = con_maximo.next()?;
+            if con_maximo.next().is_some() { return None; }
+            Some(*id)
+        }
+
+        /// Porcentaje de `parte` sobre `total` en centésimos de punto, truncado. Es cero si el total es cero.
+        fn porcentaje(parte:u128, total:u128) -> Result<u64>
+        {
+            if total == 0 { return Ok(0); }
+            let porcentaje = parte.checked_mul(10_000).and_then(|escalado| escalado.checked_div(total)).ok_or(Error::OVERFLOW)?;
+            u64::try_from(porcentaje).map_err(|_| Error::OVERFLOW)
+        }
+
+        /// Máximo común divisor por el algoritmo de Euclides.
+        fn mcd(mut a:u128, mut b:u128) -> Result<u128>
+        {
+            while b != 0 {
+                let resto = a.checked_rem(b).ok_or(Error::OVERFLOW)?;
+                a = b;
+                b = resto;
+            }
+            Ok(a)
+        }
+
+        /// Mínimo común múltiplo de dos números positivos.
+        fn mcm(a:u128, b:u128) -> Result<u128>
+        {
+            let divisor = Self::mcd(a, b)?;
+            a.checked_div(divisor).and_then(|cociente| cociente.checked_mul(b)).ok_or(Error::OVERFLOW)
+        }
+
+        /// Más del 45% de la base, o al menos el 40% con más de 10 puntos de diferencia.
+        /// Se compara multiplicando en lugar de dividir, por lo que no hay redondeo.
+        fn gana_en_primera_vuelta(votos_primero:u64, votos_segundo:u64, base:u64) -> Result<bool>
+        {
+            let por_cien = |votos:u64| (votos as u128).checked_mul(100).ok_or(Error::OVERFLOW);
+            let total = base as u128;
+            let primero = por_cien(votos_primero)?;
+            let diferencia = por_cien(votos_primero.checked_sub(votos_segundo).ok_or(Error::OVERFLOW)?)?;
+            let porcentaje_del_total = |puntos:u128| total.checked_mul(puntos).ok_or(Error::OVERFLOW);
+            Ok(primero > porcentaje_del_total(45)?
+                || (primero >= porcentaje_del_total(40)? && diferencia > porcentaje_del_total(10)?))
+        }
+
+        /// Crea la segunda vuelta sin fechas, con los finalistas como candidatos y el padrón vacío.
+        /// El padrón de la primera vuelta se copia por partes con `copiar_padron_segunda_vuelta`, de forma que
+        /// el costo del escrutinio no dependa de la cantidad de votantes.
+        fn crear_segunda_vuelta(&mut self, eleccion: &mut Eleccion, finalistas: &[AccountId]) -> Result<()>
+        {
+            let configuracion = ConfiguracionEleccion { balotaje: false, ..eleccion.configuracion.clone() };
+            let mut segunda_vuelta = self.nueva_eleccion(0, 0, configuracion, Some(eleccion.id))?;
+            if let Some(descripcion) = self.descripciones.get(eleccion.id) {
+                self.descripciones.insert(segunda_vuelta.id, &descripcion);
+            }
+            // Los finalistas reciben ids nuevos en la segunda vuelta y conservan su plataforma.
+            for usuario_id in finalistas {
+                let candidato_id = self.agregar_candidato(&mut segunda_vuelta, *usuario_id, 0)?;
+                let plataforma = self.candidato_por_usuario.get((eleccion.id, *usuario_id))
+                    .and_then(|candidato_anterior| self.plataformas.get((eleccion.id, candidato_anterior)));
+                if let Some(plataforma) = plataforma {
+                    self.plataformas.insert((segunda_vuelta.id, candidato_id), &plataforma);
+                }
+            }
+            self.guardar_eleccion(&segunda_vuelta);
+            eleccion.segunda_vuelta = Some(segunda_vuelta.id);
+            self.env().emit_event(SegundaVueltaCreada { eleccion_id: eleccion.id, segunda_vuelta_id: segunda_vuelta.id });
+            Ok(())
+        }
+
+        /// Segunda vuelta instantánea.
+        /// En cada ronda cada boleta cuenta para su candidato mejor ubicado que siga en carrera.
+        /// Si alguno supera la mitad de los votos válidos de la ronda (más los blancos, si integran la base), gana.
+        /// Si no, se elimina al menos votado y se pasa a la siguiente ronda. Ante un empate en el último lugar
+        /// se elimina al de mayor id, es decir, al último en inscribirse, salvo que todos los que siguen en carrera
+        /// estén empatados: entonces el escrutinio termina sin ganador, como en los demás sistemas.
+        /// Si los blancos integran la base y ningún candidato logra superar la mitad, se eliminan todos y no hay ganador.
+        /// Cada ronda vuelve a leer todas las boletas, así que el escrutinio se hace por partes: cada llamada lee hasta
+        /// `boletas` boletas (nunca más de MAXIMO_BOLETAS_POR_LLAMADA) y guarda dónde quedó. Devuelve si terminó.
+        fn escrutar_preferencial(&mut self, eleccion: &mut Eleccion, boletas:u64) -> Result<bool>
+        {
+            let mut escrutinio = match self.escrutinios_preferenciales.get(eleccion.id) {
+                Some(escrutinio) => escrutinio,
+                None => {
+                    let en_carrera:Vec<u64> = self.obtener_candidatos_de_eleccion(eleccion).iter().map(|candidato| candidato.id).collect();
+                    EscrutinioPreferencial { votos: ink::prelude::vec![0; en_carrera.len()], en_carrera, boletas_agotadas: 0, boletas_contadas: 0 }
+                },
+            };
+            let mut rondas = self.rondas_escrutinio.get(eleccion.id).unwrap_or_default();
+            let mut boletas_restantes = boletas.min(MAXIMO_BOLETAS_POR_LLAMADA);
+
+            loop {
+                let hasta = escrutinio.boletas_contadas.saturating_add(boletas_restantes).min(eleccion.cantidad_boletas);
+                for indice in escrutinio.boletas_contadas..hasta {
+                    let boleta = self.boletas.get((eleccion.id, indice)).unwrap_or_default();
+                    match boleta.iter().find_map(|candidato_id| escrutinio.en_carrera.iter().position(|id| id == candidato_id)) {
+                        Some(posicion) => escrutinio.votos[posicion] = escrutinio.votos[posicion].checked_add(1).ok_or(Error::OVERFLOW)?,
+                        None => escrutinio.boletas_agotadas = escrutinio.boletas_agotadas.checked_add(1).ok_or(Error::OVERFLOW)?,
+                    }
+                }
+                boletas_restantes = boletas_restantes.checked_sub(hasta.checked_sub(escrutinio.boletas_contadas).ok_or(Error::OVERFLOW)?).ok_or(Error::OVERFLOW)?;
+                escrutinio.boletas_contadas = hasta;
+                if hasta < eleccion.cantidad_boletas {
+                    self.escrutinios_preferenciales.insert(eleccion.id, &escrutinio);
+                    self.rondas_escrutinio.insert(eleccion.id, &rondas);
+                    return Ok(false);
+                }
+
+                let (ronda, termino) = Self::cerrar_ronda_preferencial(eleccion, &mut escrutinio)?;
+                rondas.push(ronda);
+                if termino {
+                    eleccion.ganador = rondas.last().and_then(|ronda| ronda.ganador);
+                    self.escrutinios_preferenciales.remove(eleccion.id);
+                    self.rondas_escrutinio.insert(eleccion.id, &rondas);
+                    return Ok(true);
+                }
+            }
+        }
+
+        /// Cierra la ronda que terminó de contar `escrutinio` y devuelve la ronda y si el escrutinio terminó.
+        /// Si no terminó, elimina al candidato que corresponde y deja el escrutinio listo para la ronda siguiente.
+        fn cerrar_ronda_preferencial(eleccion: &Eleccion, escrutinio: &mut EscrutinioPreferencial) -> Result<(RondaEscrutinio, bool)>
+        {
+            let votos_validos = eleccion.cantidad_boletas.checked_sub(escrutinio.boletas_agotadas).ok_or(Error::OVERFLOW)?;
+            let conteo:Vec<ConteoRonda> = escrutinio.en_carrera.iter().zip(escrutinio.votos.iter())
+                .map(|(candidato_id, votos)| ConteoRonda { candidato_id: *candidato_id, votos: *votos })
+                .collect();
+            let mut ronda = RondaEscrutinio { conteo, boletas_agotadas: escrutinio.boletas_agotadas, eliminado: None, ganador: None };
+
+            let maximo = escrutinio.votos.iter().copied().max().unwrap_or(0);
+            if votos_validos == 0 { return Ok((ronda, true)); }
+            if maximo.checked_mul(2).ok_or(Error::OVERFLOW)? > eleccion.base_porcentual(votos_validos)? {
+                ronda.ganador = escrutinio.votos.iter().position(|v| *v == maximo).map(|posicion| escrutinio.en_carrera[posicion]);
+                return Ok((ronda, true));
+            }
+
+            let minimo = escrutinio.votos.iter().copied().min().unwrap_or(0);
+            if escrutinio.en_carrera.len() > 1 && minimo == maximo { return Ok((ronda, true)); }
+            let posicion = escrutinio.votos.iter().rposition(|v| *v == minimo).ok_or(Error::CANDIDATO_NO_EXISTE)?;
+            ronda.eliminado = Some(escrutinio.en_carrera.remove(posicion));
+            escrutinio.votos = ink::prelude::vec![0; escrutinio.en_carrera.len()];
+            escrutinio.boletas_agotadas = 0;
+            escrutinio.boletas_contadas = 0;
+            Ok((ronda, false))
+        }
+
+        /// Reparte las bancas entre las listas que superan el umbral con el método D'Hondt: cada banca va a la lista
+        /// con mayor cociente votos / (bancas obtenidas + 1). Los cocientes se comparan multiplicando, sin redondeo.
+        /// Ante cocientes iguales gana la lista con más votos y, si también empatan, la de menor id.
+        /// Una lista que ya ocupó a todos sus candidatos no recibe más bancas; si ninguna puede recibirlas, quedan vacantes.
+        fn repartir_bancas(&self, eleccion: &Eleccion, bancas:u64, umbral:u64) -> Result<Vec<ResultadoLista>>
+        {
+            let listas = self.obtener_listas_de_eleccion(eleccion);
+            let mut votos_validos:u64 = 0;
+            let mut resultados = Vec::new();
+            for lista in listas.iter() {
+                let votos = self.votos_por_lista.get((eleccion.id, lista.id)).unwrap_or(0);
+                votos_validos = votos_validos.checked_add(votos).ok_or(Error::OVERFLOW)?;
+                resultados.push(ResultadoLista { lista_id: lista.id, votos, supera_umbral: false, bancas: 0, electos: Vec::new() });
+            }
+            let base = eleccion.base_porcentual(votos_validos)? as u128;
+            for resultado in resultados.iter_mut() {
+                let votos = (resultado.votos as u128).checked_mul(10_000).ok_or(Error::OVERFLOW)?;
+                resultado.supera_umbral = resultado.votos > 0 && votos >= (umbral as u128).checked_mul(base).ok_or(Error::OVERFLOW)?;
+            }
+
+            for _ in 0..bancas {
+                let mut elegida:Option<usize> = None;
+                for (posicion, resultado) in resultados.iter().enumerate() {
+                    if !resultado.supera_umbral || resultado.electos.len() >= listas[posicion].candidatos.len() { continue; }
+                    let mejor = match elegida {
+                        None => true,
+                        Some(actual) => Self::cociente_mayor(resultado, &resultados[actual])?,
+                    };
+                    if mejor { elegida = Some(posicion); }
+                }
+                let Some(posicion) = elegida else { break; };
+                let resultado = &mut resultados[posicion];
+                resultado.electos.push(listas[posicion].candidatos[resultado.electos.len()].candidato_id);
+                resultado.bancas = resultado.bancas.checked_add(1).ok_or(Error::OVERFLOW)?;
+            }
+            Ok(resultados)
+        }
+
+        /// Indica si el cociente D'Hondt de `a` supera al de `b`, o si empatan y `a` tiene más votos.
+        fn cociente_mayor(a: &ResultadoLista, b: &ResultadoLista) -> Result<bool>
+        {
+            let producto = |votos:u64, bancas:u64| -> Result<u128> {
+                (votos as u128).checked_mul((bancas as u128).checked_add(1).ok_or(Error::OVERFLOW)?).ok_or(Error::OVERFLOW)
+            };
+            let cociente_a = producto(a.votos, b.bancas)?;
+            let cociente_b = producto(b.votos, a.bancas)?;
+            Ok(cociente_a > cociente_b || (cociente_a == cociente_b && a.votos > b.votos))
+        }
+
+        fn obtener_listas_de_eleccion(&self, eleccion: &Eleccion) -> Vec<Lista> {
+            (1..=eleccion.cantidad_listas)
+                .filter_map(|lista_id| self.listas.get((eleccion.id, lista_id)))
+                .collect()
+        }
+
+        /// Compromiso de un voto secreto: blake2x256 de la codificación SCALE de (eleccion_id, votante, candidato_id, sal).
+        /// Incluir la elección y el votante impide que otro votante copie un compromiso ajeno y lo revele como propio.
+        fn hash_compromiso(eleccion_id:u64, votante:AccountId, candidato_id:u64, sal:&[u8; 32]) -> Hash
+        {
+            calcular_hash(&(eleccion_id, votante, candidato_id, sal))
+        }
+
+        fn rango_inscripciones(&self, eleccion_id:u64) -> RangoLista {
+            self.rangos_inscripciones.get(eleccion_id).unwrap_or_default()
+        }
+        fn primera_inscripcion_pendiente(&self, eleccion_id:u64) -> Option<SolicitudInscripcion> {
+            let posicion = self.rango_inscripciones(eleccion_id).primera()?;
+            self.inscripciones_pendientes.get((eleccion_id, posicion))
+        }
+        fn obtener_inscripcion_pendiente(&self, eleccion_id:u64, usuario:AccountId) -> Option<SolicitudInscripcion> {
+            let posicion = self.posicion_de_inscripcion.get((eleccion_id, usuario))?;
+            self.inscripciones_pendientes.get((eleccion_id, posicion))
+        }
+
+        /// Agrega la solicitud al final de la cola de la elección.
+        fn encolar_inscripcion(&mut self, eleccion_id:u64, usuario:AccountId, tipo:TIPO_DE_USUARIO) -> Result<()> {
+            let mut rango = self.rango_inscripciones(eleccion_id);
+            let posicion = rango.agregar()?;
+            self.inscripciones_pendientes.insert((eleccion_id, posicion), &SolicitudInscripcion { usuario_id: usuario, tipo });
+            self.posicion_de_inscripcion.insert((eleccion_id, usuario), &posicion);
+            self.rangos_inscripciones.insert(eleccion_id, &rango);
+            self.inscripciones.insert((eleccion_id, usuario), &ESTADO_INSCRIPCION::PENDIENTE);
+            Ok(())
+        }
+        /// Saca la solicitud del usuario de la cola de la elección.
+        fn desencolar_inscripcion(&mut self, eleccion_id:u64, usuario:AccountId) -> Result<()> {
+            let posicion = self.posicion_de_inscripcion.take((eleccion_id, usuario)).ok_or(Error::INSCRIPCION_NO_PENDIENTE)?;
+            let mut rango = self.rango_inscripciones(eleccion_id);
+            Self::quitar_de_lista(&mut self.inscripciones_pendientes, |posicion| (eleccion_id, posicion), &mut rango, posicion)?;
+            self.rangos_inscripciones.insert(eleccion_id, &rango);
+            Ok(())
+        }
+
+        ///Usado por el administrador.
+        ///Revisa el primer usuario pendiente de la elección.
+        ///Lo agrega como candidato si es candidato, o votante en caso contrario.
+        /// Devuelve el id del usuario procesado.
+        fn procesar_siguiente_usuario_pendiente_en_eleccion(&mut self, eleccion: &mut Eleccion, aceptar_usuario:bool) -> Result<AccountId>
+        {
+            let solicitud = self.primera_inscripcion_pendiente(eleccion.id).ok_or(Error::NO_HAY_USUARIOS_PENDIENTES)?;
+            self.procesar_usuario_pendiente_en_eleccion(eleccion, solicitud.usuario_id, Self::motivo_si_rechaza(aceptar_usuario))
+        }
+
+        /// Acepta o rechaza la solicitud de inscripción de un usuario en la elección, esté donde esté en la cola.
+        /// Con `None` la acepta; con un motivo la rechaza y deja el rechazo registrado.
+        /// Los candidatos solo pueden aceptarse antes de que empiece la votación; los votantes, también durante.
+        /// Si devuelve un error no modifica nada, por lo que puede usarse dentro de un lote.
+        fn procesar_usuario_pendiente_en_eleccion(&mut self, eleccion: &mut Eleccion, usuario:AccountId, motivo_rechazo:Option<MotivoRechazo>) -> Result<AccountId>
+        {
+            let SolicitudInscripcion { usuario_id: usuario, tipo } = self.obtener_inscripcion_pendiente(eleccion.id, usuario)
+                .ok_or(Error::INSCRIPCION_NO_PENDIENTE)?;
+
+            let Some(motivo) = motivo_rechazo else {
+                let candidato_asignado = match tipo {
+                    TIPO_DE_USUARIO::VOTANTE => {
+                        let claustro = self.validar_claustro(eleccion, usuario)?;
+                        self.agregar_votante(eleccion, Votante {
+                            usuario_id: usuario,
+                            voto_emitido: false,
+                            claustro,
+                        })?;
+                        None
+                    },
+                    TIPO_DE_USUARIO::CANDIDATO => {
+                        // Las boletas ya emitidas no podrían incluir a un candidato nuevo.
+                        eleccion.validar_sin_iniciar()?;
+                        Some(self.agregar_candidato(eleccion, usuario, 0)?)
+                    },
+                };
+                self.desencolar_inscripcion(eleccion.id, usuario)?;
+                self.guardar_eleccion(eleccion);
+                self.env().emit_event(InscripcionAceptada {
+                    eleccion_id: eleccion.id,
+                    usuario,
+                    tipo,
+                    candidato_id: candidato_asignado,
+                });
+                return Ok(usuario);
+            };
+
+            self.desencolar_inscripcion(eleccion.id, usuario)?;
+            self.inscripciones.insert((eleccion.id, usuario), &ESTADO_INSCRIPCION::RECHAZADA);
+            self.env().emit_event(InscripcionRechazada { eleccion_id: eleccion.id, usuario, codigo: motivo.codigo.clone() });
+            self.registrar_rechazo(usuario, Some(eleccion.id), Some(tipo), motivo);
+            Ok(usuario)
+        }
+
+        /// Vuelve a poner en la cola de la elección la inscripción rechazada de un usuario.
+        /// Las inscripciones rechazadas importadas no conservan el tipo y se reincorporan como votante.
+        fn reincorporar_inscripcion(&mut self, eleccion_id:u64, usuario:AccountId, tipo:Option<TIPO_DE_USUARIO>) -> Result<()> {
+            self.encolar_inscripcion(eleccion_id, usuario, tipo.unwrap_or(TIPO_DE_USUARIO::VOTANTE))
+        }
+
+
+    // ===================================================================================================
+    // =========================creacion y administracion de usuarios=====================================
+    // ===================================================================================================
+
+        /// Utilizado por los usuarios para poder registrarse en el sistema.
+        /// Luego de registrarse queda pendiente de aceptación por parte de un Administrador.
+        /// Si tu registro es rechazado, no podrás volver a intentar registrarte.
+        /// En lugar del DNI se envía su hash, calculado por la aplicación de registro con `hash_dni`, que valida
+        /// el formato. Opcionalmente se puede enviar una copia cifrada con la clave pública del administrador,
+        /// que solo él podrá descifrar y que permite comprobar el hash antes de aprobar al usuario.
+        /// Los argumentos quedan públicos en la transacción, y el nombre y el apellido también en el storage.
+        #[ink(message)]
+        pub fn registrarse(&mut self, nombre:String, apellido:String, dni_hash:Hash, dni_cifrado:Option<Vec<u8>>) -> Result<()>
+        {
+            if !self.registro_activado { return Err(Error::REGISTRO_NO_ACTIVADO); }
+            if self.es_administrador() { return Err(Error::ES_ADMINISTRADOR); }
+
+            let id = self.env().caller();
+            if self.es_usuario_rechazado(id) {  return Err(Error::USUARIO_RECHAZADO); }
+            if self.es_usuario_registrado(id) { return Err(Error::USUARIO_YA_REGISTRADO); }
+            if self.es_usuario_pendiente(id) { return Err(Error::USUARIO_YA_PENDIENTE); }
+
+            if dni_hash == Hash::default() { return Err(Error::DNI_INVALIDO); }
+            if self.usuario_por_dni.contains(dni_hash) { return Err(Error::DNI_YA_REGISTRADO); }
+            let usuario = Usuario { id, nombre, apellido, dni_hash, dni_cifrado, estado: ESTADO_USUARIO::PENDIENTE };
+            self.agregar_usuario(usuario)?;
+            self.env().emit_event(UsuarioRegistrado { usuario: id });
+            Ok(())
+        }
+
+        /// Utilizado por un administrador o un registrador.
+        /// Obtiene la información del próximo usuario a registrarse.
+        #[ink(message)]
+        pub fn obtener_informacion_siguiente_usuario_pendiente(&self) -> Result<PerfilUsuario>
+        {
+            self.verificar_permiso(PERMISO::APROBAR_USUARIOS)?;
+            let sig_usuario = self.primer_usuario_pendiente().and_then(|id| self.usuarios.get(id));
+            match sig_usuario {
+                Some(usuario) => Ok(PerfilUsuario::from(usuario)),
+                None => Err(Error::NO_HAY_USUARIOS_PENDIENTES),
+            }
+        }
+
+        /// Utilizado por un administrador o un registrador.
+        /// Se procesará el próximo usuario pendiente.
+        /// Para obtener la información del mismo, utilizar obtenerInformacionSiguienteUsuarioPendiente
+        /// Si se acepta el usuario, podrá utilizar el sistema.
+        /// Si se rechaza el usuario, este no podrá volver a intentar registrarse en el sistema.
+        /// Devuelve el id del usuario procesado.
+        #[ink(message)]
+        pub fn procesar_siguiente_usuario_pendiente(&mut self, aceptar_usuario:bool) -> Result<AccountId>
+        {
+            self.verificar_permiso(PERMISO::APROBAR_USUARIOS)?;
+            let usuario_id = self.primer_usuario_pendiente().ok_or(Error::NO_HAY_USUARIOS_PENDIENTES)?;
+            self.procesar_usuario(usuario_id, Self::motivo_si_rechaza(aceptar_usuario))?;
+            Ok(usuario_id)
+        }
+
+        /// Utilizado por un administrador o un registrador.
+        /// Acepta o rechaza a un usuario pendiente en particular, sin importar su lugar en la cola.
+        /// Los demás pendientes conservan su lugar.
+        #[ink(message)]
+        pub fn procesar_usuario_pendiente(&mut self, usuario_id:AccountId, aceptar_usuario:bool) -> Result<()>
+        {
+            self.verificar_permiso(PERMISO::APROBAR_USUARIOS)?;
+            self.procesar_usuario(usuario_id, Self::motivo_si_rechaza(aceptar_usuario))
+        }
+
+        /// Utilizado por un administrador o un registrador.
+        /// Acepta o rechaza varios usuarios pendientes, estén donde estén en la cola. Un error en un usuario no afecta
+        /// a los demás: se devuelve el resultado de cada uno en el mismo orden en que se ingresaron.
+        #[ink(message)]
+        pub fn procesar_lote_usuarios_pendientes(&mut self, usuarios:Vec<AccountId>, aceptar_usuario:bool) -> Result<Vec<ResultadoProcesamiento>>
+        {
+            self.verificar_permiso(PERMISO::APROBAR_USUARIOS)?;
+            Ok(usuarios.into_iter().map(|usuario| ResultadoProcesamiento {
+                usuario,
+                resultado: self.procesar_usuario(usuario, Self::motivo_si_rechaza(aceptar_usuario)),
+            }).collect())
+        }
+
+        /// Utilizado por un administrador o un registrador.
+        /// Rechaza a un usuario pendiente indicando el motivo, que queda registrado y el usuario puede consultar.
+        #[ink(message)]
+        pub fn rechazar_usuario_pendiente(&mut self, usuario_id:AccountId, motivo:MotivoRechazo) -> Result<()>
+        {
+            self.verificar_permiso(PERMISO::APROBAR_USUARIOS)?;
+            Self::validar_longitud_texto(&motivo.detalle)?;
+            self.procesar_usuario(usuario_id, Some(motivo))
+        }
+
+        /// Las consultas de usuarios recorren las posiciones de la lista a partir de la del primero, salteando `desde`
+        /// y revisando hasta `cantidad` (como máximo MAXIMO_POR_PAGINA). Un usuario que sale de la lista deja vacía su
+        /// posición, así que una página puede traer menos de `cantidad` cuentas: para recorrer la lista completa se
+        /// avanza `desde` de a `cantidad` hasta reunir `obtener_cantidad_usuarios`. Todas salen en orden de llegada.
+        #[ink(message)]
+        pub fn obtener_usuarios_pendientes(&self, desde:u64, cantidad:u64) -> Vec<AccountId>
+        {
+            self.pagina_de_usuarios(ESTADO_USUARIO::PENDIENTE, desde, cantidad)
+        }
+        #[ink(message)]
+        pub fn obtener_usuarios(&self, desde:u64, cantidad:u64) -> Vec<AccountId>
+        {
+            self.pagina_de_usuarios(ESTADO_USUARIO::ACEPTADO, desde, cantidad)
+        }
+        #[ink(message)]
+        pub fn obtener_usuarios_rechazados(&self, desde:u64, cantidad:u64) -> Vec<AccountId>
+        {
+            self.pagina_de_usuarios(ESTADO_USUARIO::RECHAZADO, desde, cantidad)
+        }
+        /// Cantidad de usuarios en el estado indicado, para recorrer las consultas paginadas.
+        #[ink(message)]
+        pub fn obtener_cantidad_usuarios(&self, estado:ESTADO_USUARIO) -> u64
+        {
+            self.rango_usuarios(&estado).cantidad()
+        }
+
+        /// Utilizado por un administrador o un auditor.
+        /// Devuelve la cuenta que registró el DNI con el hash ingresado (ver `hash_dni`), sin importar si está
+        /// pendiente, aceptada o rechazada.
+        #[ink(message)]
+        pub fn obtener_usuario_por_dni(&self, dni_hash:Hash) -> Result<AccountId>
+        {
+            self.verificar_permiso(PERMISO::LEER_DATOS_PERSONALES)?;
+            self.usuario_por_dni.get(dni_hash).ok_or(Error::USUARIO_NO_ENCONTRADO)
+        }
+
+        /// Utilizado por un administrador o un auditor.
+        /// Devuelve las cuentas importadas que comparten DNI con otra cuenta, junto con la primera que lo registró.
+        /// Paginada como `obtener_usuarios`.
+        #[ink(message)]
+        pub fn obtener_posibles_duplicados(&self, desde:u64, cantidad:u64) -> Result<Vec<PosibleDuplicado>>
+        {
+            self.verificar_permiso(PERMISO::LEER_DATOS_PERSONALES)?;
+            Ok(Self::pagina_de_lista(&self.posibles_duplicados, |posicion| posicion, self.rango_posibles_duplicados, desde, cantidad))
+        }
+
+        /// Utilizado por el propio usuario, un administrador, un auditor o un registrador.
+        #[ink(message)]
+        pub fn obtener_datos_usuario_pendiente_por_id(&self, id_usuario: AccountId) -> Result<PerfilUsuario>
+        {
+            if !self.puede_leer_datos_de(id_usuario, &[PERMISO::LEER_DATOS_PERSONALES, PERMISO::APROBAR_USUARIOS]) {
+                return Err(Error::SIN_PERMISO);
+            }
+            let sig_usuario = self.obtener_usuario_pendiente_por_id(id_usuario);
+            match sig_usuario {
+                Some(usuario) => Ok(PerfilUsuario::from(usuario)),
+                None => Err(Error::USUARIO_NO_ENCONTRADO),
+            }
+        }
+        /// Utilizado por el propio usuario, un administrador o un auditor.
+        #[ink(message)]
+        pub fn obtener_datos_usuario_por_id(&self, id_usuario: AccountId) -> Result<PerfilUsuario>
+        {
+            if !self.puede_leer_datos_de(id_usuario, &[PERMISO::LEER_DATOS_PERSONALES]) { return Err(Error::SIN_PERMISO); }
+            let sig_usuario = self.obtener_usuario_por_id(id_usuario);
+            match sig_usuario {
+                Some(usuario) => Ok(PerfilUsuario::from(usuario)),
+                None => Err(Error::USUARIO_NO_ENCONTRADO),
+            }
+        }
+        /// Utilizado por el propio usuario, un administrador o un auditor.
+        #[ink(message)]
+        pub fn obtener_datos_usuario_rechazado_por_id(&self, id_usuario: AccountId) -> Result<PerfilUsuario>
+        {
+            if !self.puede_leer_datos_de(id_usuario, &[PERMISO::LEER_DATOS_PERSONALES]) { return Err(Error::SIN_PERMISO); }
+            let sig_usuario = self.obtener_usuario_rechazado_por_id(id_usuario);
+            match sig_usuario {
+                Some(usuario) => Ok(PerfilUsuario::from(usuario)),
+                None => Err(Error::USUARIO_NO_ENCONTRADO),
+            }
+        }
+
+        /// Utilizado por un administrador o un auditor.
+        /// Devuelve la copia cifrada del DNI que envió el usuario al registrarse, si la envió.
+        #[ink(message)]
+        pub fn obtener_dni_cifrado(&self, id_usuario: AccountId) -> Result<Option<Vec<u8>>>
+        {
+            self.verificar_permiso(PERMISO::LEER_DATOS_PERSONALES)?;
+            self.usuarios.get(id_usuario)
+                .map(|usuario| usuario.dni_cifrado)
+                .ok_or(Error::USUARIO_NO_ENCONTRADO)
+        }
+
+        /// Utilizado por un usuario registrado.
+        /// Declara o cambia su género. Las listas ya presentadas conservan el género declarado al presentarlas.
+        #[ink(message)]
+        pub fn declarar_genero(&mut self, genero:GENERO) -> Result<()>
+        {
+            let id = self.env().caller();
+            if !self.es_usuario_registrado(id) { return Err(Error::USUARIO_NO_REGISTRADO); }
+            self.generos.insert(id, &genero);
+            Ok(())
+        }
+
+        /// Utilizado por el propio usuario o un auditor.
+        /// Devuelve el género declarado por el usuario, si lo declaró.
+        #[ink(message)]
+        pub fn obtener_genero(&self, id_usuario: AccountId) -> Result<Option<GENERO>>
+        {
+            if !self.puede_leer_datos_de(id_usuario, &[PERMISO::LEER_DATOS_PERSONALES]) { return Err(Error::SIN_PERMISO); }
+            Ok(self.generos.get(id_usuario))
+        }
+
+        /// Utilizado por un administrador o un registrador.
+        /// Asigna, cambia o quita (con None) el claustro de un usuario pendiente o aceptado.
+        /// Los padrones de elecciones ponderadas ya armados conservan el claustro que tenía al ser aceptado.
+        #[ink(message)]
+        pub fn asignar_claustro(&mut self, usuario:AccountId, claustro:Option<CLAUSTRO>) -> Result<()>
+        {
+            self.verificar_permiso(PERMISO::APROBAR_USUARIOS)?;
+            if !self.es_usuario_registrado(usuario) && !self.es_usuario_pendiente(usuario) { return Err(Error::USUARIO_NO_ENCONTRADO); }
+            match &claustro {
+                Some(claustro) => { self.claustros.insert(usuario, claustro); },
+                None => self.claustros.remove(usuario),
+            }
+            self.env().emit_event(ClaustroAsignado { usuario, claustro });
+            Ok(())
+        }
+
+        /// Devuelve el claustro asignado al usuario, si tiene uno.
+        #[ink(message)]
+        pub fn obtener_claustro(&self, usuario:AccountId) -> Option<CLAUSTRO>
+        {
+            self.claustros.get(usuario)
+        }
+
+        /// Utilizado por un administrador o un registrador.
+        /// Activa el registro de usuarios si no está activo el registro.
+        #[ink(message)]
+        pub fn activar_registro(&mut self) -> Result<()> 
+        {
+            self.verificar_permiso(PERMISO::GESTIONAR_REGISTRO)?;
+            if self.registro_activado { return Err(Error::REGISTRO_YA_ACTIVADO); }
+            self.registro_activado = true;
+            self.env().emit_event(RegistroCambiado { activado: true });
+            Ok(())
+        }
+        /// Utilizado por un administrador o un registrador.
+        /// Desactiva el registro de usuarios. Falla si el registro no está activo.
+        #[ink(message)]
+        pub fn desactivar_registro(&mut self) -> Result<()> 
+        {
+            self.verificar_permiso(PERMISO::GESTIONAR_REGISTRO)?;
+            if !self.registro_activado { return Err(Error::REGISTRO_NO_ACTIVADO); }
+            self.registro_activado = false;
+            self.env().emit_event(RegistroCambiado { activado: false });
+            Ok(())
+        }
+
+
+    // ===================================================================================================
+    // ===================================================================================================
+    // ===================================================================================================
+
+
+    // ===================================================================================================
+    // =========================creacion y administracion de estados de elecciones========================
+    // ===================================================================================================
+
+        /// Utilizado por un administrador.
+        /// Crea una elección colocando fecha de inicio y final.
+        /// En la configuración se elige si el voto es DIRECTO o SECRETO (compromiso y revelación).
+        /// Los referéndums se crean con `crear_referendum`.
+        /// Devuelve el id de la elección creada.
+        #[ink(message)]
+        pub fn crear_eleccion(&mut self, fecha_inicial:String, fecha_final:String, configuracion:ConfiguracionEleccion) -> Result<u64>
+        {
+            self.verificar_permiso(PERMISO::CREAR_ELECCIONES)?;
+            if configuracion.sistema_escrutinio == SISTEMA_ESCRUTINIO::REFERENDUM { return Err(Error::CONFIGURACION_INVALIDA); }
+            self.registrar_eleccion(&fecha_inicial, &fecha_final, configuracion)
+        }
+
+        /// Utilizado por un administrador.
+        /// Crea un referéndum sobre la propuesta, con el sistema de escrutinio REFERENDUM y voto DIRECTO.
+        /// `blancos_en_base` indica si los votos en blanco integran la base sobre la que se calcula la mayoría.
+        /// El padrón se arma con `inscribir_usuario_en_eleccion` como en cualquier elección, pero solo se aceptan votantes.
+        /// Devuelve el id de la elección creada.
+        #[ink(message)]
+        pub fn crear_referendum(&mut self, fecha_inicial:String, fecha_final:String, blancos_en_base:bool, propuesta:Propuesta) -> Result<u64>
+        {
+            self.verificar_permiso(PERMISO::CREAR_ELECCIONES)?;
+            propuesta.validar()?;
+            let configuracion = ConfiguracionEleccion {
+                sistema_escrutinio: SISTEMA_ESCRUTINIO::REFERENDUM,
+                blancos_en_base,
+                ..ConfiguracionEleccion::default()
+            };
+            let eleccion_id = self.registrar_eleccion(&fecha_inicial, &fecha_final, configuracion)?;
+            self.propuestas.insert(eleccion_id, &propuesta);
+            Ok(eleccion_id)
+        }
+
+        /// Utilizado por un administrador o un oficial electoral de la elección, antes de que empiece la votación.
+        /// Establece o reemplaza el título, la descripción y el cargo que se elige.
+        #[ink(message)]
+        pub fn establecer_descripcion_eleccion(&mut self, eleccion_id:u64, descripcion:DescripcionEleccion) -> Result<()>
+        {
+            self.verificar_permiso(PERMISO::GESTIONAR_ELECCION(eleccion_id))?;
+            descripcion.validar()?;
+            self.validar_eleccion_sin_iniciar(eleccion_id)?;
+            self.descripciones.insert(eleccion_id, &descripcion);
+            self.env().emit_event(DescripcionEleccionCambiada { eleccion_id });
+            Ok(())
+        }
+
+        /// Devuelve el título, la descripción y el cargo de la elección. Están vacíos si nunca se establecieron.
+        #[ink(message)]
+        pub fn obtener_descripcion_eleccion(&self, eleccion_id:u64) -> Result<DescripcionEleccion>
+        {
+            if !self.existe_eleccion(eleccion_id) { return Err(Error::ELECCION_NO_EXISTE); }
+            Ok(self.descripciones.get(eleccion_id).unwrap_or_default())
+        }
+
+        /// Utilizado por un administrador o un oficial electoral de la elección.
+        /// Establece las fechas de una segunda vuelta creada por el escrutinio de otra elección.
+        /// Hasta entonces la segunda vuelta no puede abrirse, iniciarse ni recibir votos.
+        /// Las fechas solo se pueden establecer una vez, así que la inicial debe ser futura y anterior a la final.
+        /// Antes hay que terminar de copiar el padrón con `copiar_padron_segunda_vuelta`.
+        #[ink(message)]
+        pub fn programar_segunda_vuelta(&mut self, eleccion_id:u64, fecha_inicial:String, fecha_final:String) -> Result<()>
+        {
+            self.verificar_permiso(PERMISO::GESTIONAR_ELECCION(eleccion_id))?;
+
+            let mut eleccion = self.actualizar_eleccion(eleccion_id)?;
+            let eleccion_anterior = eleccion.eleccion_anterior.ok_or(Error::NO_ES_SEGUNDA_VUELTA)?;
+            if eleccion.tiene_fechas() { return Err(Error::SEGUNDA_VUELTA_YA_PROGRAMADA); }
+            let votantes_primera_vuelta = self.elecciones.get(eleccion_anterior).ok_or(Error::ELECCION_NO_EXISTE)?.cantidad_votantes;
+            if eleccion.cantidad_votantes < votantes_primera_vuelta { return Err(Error::PADRON_INCOMPLETO); }
+
+            let (fecha_inicio, fecha_final) = Self::convertir_fechas(&fecha_inicial, &fecha_final)?;
+            if fecha_inicio <= self.env().block_timestamp() || fecha_inicio >= fecha_final { return Err(Error::CONFIGURACION_INVALIDA); }
+            eleccion.fecha_inicio = fecha_inicio;
+            eleccion.fecha_final = fecha_final;
+            self.guardar_eleccion(&eleccion);
+            self.env().emit_event(SegundaVueltaProgramada { eleccion_id, fecha_inicio, fecha_final });
+            Ok(())
+        }
+
+        /// Utilizado por un administrador o un oficial electoral de la elección.
+        /// Copia al padrón de una segunda vuelta todavía sin programar hasta `cantidad` votantes del padrón de la
+        /// primera vuelta (como máximo MAXIMO_POR_PAGINA), a continuación de los ya copiados.
+        /// Devuelve cuántos votantes faltan copiar; se llama hasta que devuelva cero.
+        #[ink(message)]
+        pub fn copiar_padron_segunda_vuelta(&mut self, eleccion_id:u64, cantidad:u64) -> Result<u64>
+        {
+            self.verificar_permiso(PERMISO::GESTIONAR_ELECCION(eleccion_id))?;
+
+            let mut eleccion = self.actualizar_eleccion(eleccion_id)?;
+            let eleccion_anterior = eleccion.eleccion_anterior.ok_or(Error::NO_ES_SEGUNDA_VUELTA)?;
+            if eleccion.tiene_fechas() { return Err(Error::SEGUNDA_VUELTA_YA_PROGRAMADA); }
+            let primera_vuelta = self.elecciones.get(eleccion_anterior).ok_or(Error::ELECCION_NO_EXISTE)?;
+            for votante in self.pagina_de_padron(&primera_vuelta, eleccion.cantidad_votantes, cantidad) {
+                self.agregar_votante(&mut eleccion, Votante { usuario_id: votante.usuario_id, voto_emitido: false, claustro: votante.claustro })?;
+            }
+            self.guardar_eleccion(&eleccion);
+            Ok(primera_vuelta.cantidad_votantes.saturating_sub(eleccion.cantidad_votantes))
+        }
+
+        /// Utilizado por un administrador o un oficial electoral de la elección.
+        /// cierra una elección colocando su estado en CERRADO (estado anterior al INICIADA).
+        #[ink(message)]
+        pub fn cerrar_eleccion(&mut self, eleccion_id: u64) -> Result<()>
+        {
+            self.verificar_permiso(PERMISO::GESTIONAR_ELECCION(eleccion_id))?;
+
+            let mut eleccion = self.actualizar_eleccion(eleccion_id)?;
+            if !eleccion.tiene_fechas() { return Err(Error::FECHAS_NO_DEFINIDAS); }
+            match eleccion.estado {
+                ESTADO_ELECCION::CERRADA => Err(Error::ELECCION_YA_EN_ESTADO),
+                ESTADO_ELECCION::INICIADA => Err(Error::VOTACION_INICIADA),
+                ESTADO_ELECCION::FINALIZADA => Err(Error::ELECCION_FINALIZADA),
+                ESTADO_ELECCION::ABIERTA => {
+                    eleccion.cambiar_estado(ESTADO_ELECCION::CERRADA)?;
+                    self.guardar_eleccion(&eleccion);
+                    Ok(())
+                }
+            }
+        }
+        /// Utilizado por un administrador o un oficial electoral de la elección.
+        /// abre una elección colocando su estado en ABIERTA para que los usuarios puedan inscribirse.
+        #[ink(message)]
+        pub fn abrir_eleccion(&mut self, eleccion_id: u64) -> Result<()>
+        {
+            self.verificar_permiso(PERMISO::GESTIONAR_ELECCION(eleccion_id))?;
+
+            let mut eleccion = self.actualizar_eleccion(eleccion_id)?;
+            if !eleccion.tiene_fechas() { return Err(Error::FECHAS_NO_DEFINIDAS); }
+            match eleccion.estado {
+                ESTADO_ELECCION::ABIERTA => Err(Error::ELECCION_YA_EN_ESTADO),
+                ESTADO_ELECCION::INICIADA => Err(Error::VOTACION_INICIADA),
+                ESTADO_ELECCION::FINALIZADA => Err(Error::ELECCION_FINALIZADA),
+                ESTADO_ELECCION::CERRADA => {
+                    eleccion.cambiar_estado(ESTADO_ELECCION::ABIERTA)?;
+                    self.guardar_eleccion(&eleccion);
+                    Ok(())
+                }
+            }
+        }
+        /// Utilizado por un administrador o un oficial electoral de la elección.
+        /// Registra el inicio de la votación una vez alcanzada la fecha de inicio.
+        /// El cambio ocurre igual con cualquier operación sobre la elección; este mensaje solo lo adelanta.
+        #[ink(message)]
+        pub fn iniciar_eleccion(&mut self, eleccion_id: u64) -> Result<()>
+        {
+            self.verificar_permiso(PERMISO::GESTIONAR_ELECCION(eleccion_id))?;
+
+            let estado_guardado = self.elecciones.get(eleccion_id).map(|eleccion| eleccion.estado);
+            let eleccion = self.actualizar_eleccion(eleccion_id)?;
+            if !eleccion.tiene_fechas() { return Err(Error::FECHAS_NO_DEFINIDAS); }
+            match eleccion.estado {
+                ESTADO_ELECCION::CERRADA | ESTADO_ELECCION::ABIERTA => Err(Error::VOTACION_NO_INICIADA),
+                ESTADO_ELECCION::FINALIZADA => Err(Error::ELECCION_FINALIZADA),
+                ESTADO_ELECCION::INICIADA if estado_guardado == Some(ESTADO_ELECCION::INICIADA) => Err(Error::ELECCION_YA_EN_ESTADO),
+                ESTADO_ELECCION::INICIADA => Ok(()),
+            }
+        }
+        /// Finaliza una elección y realiza su escrutinio. Puede llamarlo cualquier cuenta, ya que solo se acepta
+        /// una vez pasada la fecha final y el resultado depende únicamente de los votos.
+        /// Si la elección es secreta, el escrutinio se realiza cuando se vuelve a llamar una vez cerrada la ventana de revelación.
+        /// Si es PREFERENCIAL y el escrutinio no termina en esta llamada (ver MAXIMO_BOLETAS_POR_LLAMADA),
+        /// se continúa con `escrutar_eleccion`.
+        #[ink(message)]
+        pub fn finalizar_eleccion(&mut self, eleccion_id: u64) -> Result<()>
+        {
+            let block_timestamp = self.env().block_timestamp();
+            let estado_guardado = self.elecciones.get(eleccion_id).map(|eleccion| eleccion.estado);
+            let mut eleccion = self.actualizar_eleccion(eleccion_id)?;
+            if !eleccion.tiene_fechas() { return Err(Error::FECHAS_NO_DEFINIDAS); }
+            if !eleccion.esta_finalizada() { return Err(Error::ELECCION_NO_FINALIZADA); }
+
+            let ya_estaba_finalizada = estado_guardado == Some(ESTADO_ELECCION::FINALIZADA);
+            if eleccion.escrutada || (ya_estaba_finalizada && !eleccion.conteo_disponible(block_timestamp)) {
+                return Err(Error::ELECCION_YA_EN_ESTADO);
+            }
+            self.escrutar(&mut eleccion, MAXIMO_BOLETAS_POR_LLAMADA)?;
+            Ok(())
+        }
+
+        /// Realiza o continúa el escrutinio de una elección finalizada leyendo hasta `boletas` boletas preferenciales,
+        /// como máximo MAXIMO_BOLETAS_POR_LLAMADA. Solo el escrutinio PREFERENCIAL lee boletas, una vez por ronda,
+        /// y puede necesitar varias llamadas; los demás sistemas se escrutan en la primera.
+        /// Como `finalizar_eleccion`, puede llamarlo cualquier cuenta. Devuelve si la elección quedó escrutada.
+        #[ink(message)]
+        pub fn escrutar_eleccion(&mut self, eleccion_id:u64, boletas:u64) -> Result<bool>
+        {
+            let mut eleccion = self.actualizar_eleccion(eleccion_id)?;
+            if !eleccion.tiene_fechas() { return Err(Error::FECHAS_NO_DEFINIDAS); }
+            if !eleccion.esta_finalizada() { return Err(Error::ELECCION_NO_FINALIZADA); }
+            if eleccion.escrutada { return Err(Error::ELECCION_YA_EN_ESTADO); }
+            self.escrutar(&mut eleccion, boletas)
+        }
+
+        /// Devuelve el estado vigente de la elección según las fechas y el estado establecido por el administrador.
+        #[ink(message)]
+        pub fn obtener_estado_eleccion(&self, eleccion_id: u64) -> Result<ESTADO_ELECCION>
+        {
+            self.obtener_eleccion_por_id(eleccion_id)
+                .map(|eleccion| eleccion.estado)
+                .ok_or(Error::ELECCION_NO_EXISTE)
+        }
+
+        /// Devuelve la tabla de transiciones permitidas entre estados de una elección, como pares (desde, hacia).
+        #[ink(message)]
+        pub fn obtener_transiciones_eleccion(&self) -> Vec<(ESTADO_ELECCION, ESTADO_ELECCION)>
+        {
+            TRANSICIONES_ELECCION.to_vec()
+        }
+
+        #[ink(message)]
+        pub fn obtener_ids_elecciones(&self) -> Vec<u64>
+        {
+            (1..=self.cantidad_elecciones).collect()
+        }
+
+        /// Devuelve el resumen de una elección. Como las demás consultas de la elección, es pública: el almacenamiento
+        /// del contrato puede leerlo cualquiera, así que exigir un rol no ocultaría los datos y solo impediría auditarlos.
+        #[ink(message)]
+        pub fn obtener_datos_eleccion_por_id(&self, eleccion_id: u64) -> Result<ResumenEleccion>
+        {
+            let eleccion_option = self.obtener_eleccion_por_id(eleccion_id);
+            match eleccion_option {
+                Some(eleccion) => Ok(ResumenEleccion::from(&eleccion)),
+                None => Err(Error::ELECCION_NO_EXISTE),
+            }
+        }
+
+        /// Devuelve los candidatos con sus votos una vez que el conteo es definitivo, para cualquier cuenta.
+        #[ink(message)]
+        pub fn obtener_candidatos_eleccion_por_id(&self, eleccion_id: u64) -> Result<Vec<CandidatoConVotos>>
+        {
+            let eleccion_option = self.obtener_eleccion_por_id(eleccion_id);
+            match eleccion_option {
+                Some(eleccion) => {
+                    if !eleccion.conteo_disponible(self.env().block_timestamp()) { return Err(Error::ELECCION_NO_FINALIZADA) };
+                    Ok(self.obtener_candidatos_de_eleccion(&eleccion).into_iter().map(CandidatoConVotos::from).collect())
+                },
+                None => Err(Error::ELECCION_NO_EXISTE),
+            }
+        }
+        /// Devuelve una página del padrón como `obtener_padron_eleccion`, para cualquier cuenta. No disponible durante
+        /// la votación. Muestra quién votó pero no a quién.
+        #[ink(message)]
+        pub fn obtener_votantes_eleccion_por_id(&self, eleccion_id: u64, desde:u64, cantidad:u64) -> Result<Vec<EstadoVotante>>
+        {
+            let eleccion_option = self.obtener_eleccion_por_id(eleccion_id);
+            match eleccion_option {
+                Some(eleccion) => {
+                    if eleccion.votacion_abierta() { return Err(Error::ELECCION_NO_FINALIZADA) };
+                    Ok(self.pagina_de_padron(&eleccion, desde, cantidad).into_iter().map(EstadoVotante::from).collect())
+                },
+                None => Err(Error::ELECCION_NO_EXISTE),
+            }
+        }
+
+    // ====================================================================
+    // ====================================================================
+    // ====================================================================
+
+    // ====================================================================
+    // ===================Eleccion: usuarios===============================
+    // ====================================================================
+        /// Utilizado por un administrador o un oficial electoral de la elección.
+        /// Obtiene la información del próximo usuario a registrarse.
+        #[ink(message)]
+        pub fn obtener_siguiente_usuario_pendiente_en_una_eleccion(&self, eleccion_id:u64) -> Result<SolicitudInscripcion>
+        {
+            self.verificar_permiso(PERMISO::GESTIONAR_ELECCION(eleccion_id))?;
+            if !self.existe_eleccion(eleccion_id) { return Err(Error::ELECCION_NO_EXISTE); }
+            self.primera_inscripcion_pendiente(eleccion_id).ok_or(Error::NO_HAY_USUARIOS_PENDIENTES)
+        }
+        /// Utilizado por un administrador o un oficial electoral de la elección.
+        /// Se procesará el próximo usuario pendiente en una eleccion particular.
+        /// y se lo coloca en el vector de candidato o votante en esa eleccion segun que quiera ser.
+        /// Devuelve el id del usuario procesado.
+        #[ink(message)]
+        pub fn procesar_usuarios_en_una_eleccion(&mut self, eleccion_id:u64,aceptar_usuario:bool) -> Result<AccountId>
+        {
+            self.verificar_permiso(PERMISO::GESTIONAR_ELECCION(eleccion_id))?;
+
+            let mut eleccion_elegida = self.actualizar_eleccion(eleccion_id)?;
+            self.procesar_siguiente_usuario_pendiente_en_eleccion(&mut eleccion_elegida, aceptar_usuario)
+        }
+
+        /// Utilizado por un administrador o un oficial electoral de la elección.
+        /// Acepta o rechaza la solicitud de inscripción de un usuario en particular, sin importar su lugar en la cola.
+        /// Las demás solicitudes conservan su lugar.
+        #[ink(message)]
+        pub fn procesar_usuario_en_una_eleccion(&mut self, eleccion_id:u64, usuario:AccountId, aceptar_usuario:bool) -> Result<()>
+        {
+            self.verificar_permiso(PERMISO::GESTIONAR_ELECCION(eleccion_id))?;
+
+            let mut eleccion_elegida = self.actualizar_eleccion(eleccion_id)?;
+            self.procesar_usuario_pendiente_en_eleccion(&mut eleccion_elegida, usuario, Self::motivo_si_rechaza(aceptar_usuario))?;
+            Ok(())
+        }
+
+        /// Utilizado por un administrador o un oficial electoral de la elección.
+        /// Acepta o rechaza varias solicitudes de inscripción, estén donde estén en la cola. Un error en un usuario
+        /// no afecta a los demás: se devuelve el resultado de cada uno en el mismo orden en que se ingresaron.
+        #[ink(message)]
+        pub fn procesar_lote_en_una_eleccion(&mut self, eleccion_id:u64, usuarios:Vec<AccountId>, aceptar_usuario:bool) -> Result<Vec<ResultadoProcesamiento>>
+        {
+            self.verificar_permiso(PERMISO::GESTIONAR_ELECCION(eleccion_id))?;
+
+            let mut eleccion_elegida = self.actualizar_eleccion(eleccion_id)?;
+            Ok(usuarios.into_iter().map(|usuario| ResultadoProcesamiento {
+                usuario,
+                resultado: self.procesar_usuario_pendiente_en_eleccion(&mut eleccion_elegida, usuario, Self::motivo_si_rechaza(aceptar_usuario)).map(|_| ()),
+            }).collect())
+        }
+
+        /// Utilizado por un administrador o un oficial electoral de la elección.
+        /// Rechaza la solicitud de inscripción de un usuario indicando el motivo, que queda registrado.
+        #[ink(message)]
+        pub fn rechazar_inscripcion_en_eleccion(&mut self, eleccion_id:u64, usuario:AccountId, motivo:MotivoRechazo) -> Result<()>
+        {
+            self.verificar_permiso(PERMISO::GESTIONAR_ELECCION(eleccion_id))?;
+            Self::validar_longitud_texto(&motivo.detalle)?;
+
+            let mut eleccion_elegida = self.actualizar_eleccion(eleccion_id)?;
+            self.procesar_usuario_pendiente_en_eleccion(&mut eleccion_elegida, usuario, Some(motivo))?;
+            Ok(())
+        }
+
+
+
+        // inscribir_usuario_en_eleccion (deben ser usuarios del sistema  y no estar ya en la eleccion)
+        #[ink(message)]
+        pub fn inscribir_usuario_en_eleccion(&mut self, eleccion_id:u64, tipo:TIPO_DE_USUARIO) -> Result<()>
+        {
+            // es usuario valido en el sistema (no esta pendiente de aprobacion y no esta rechazado)
+            let id = self.env().caller();
+            if !self.es_usuario_registrado(id) { return Err(Error::USUARIO_NO_REGISTRADO); }
+
+            // el estado de la eleccion es ABIERTA
+            let eleccion = self.validar_estado_eleccion_para_inscripciones(eleccion_id)?;
+            // en una eleccion ponderada el votante debe pertenecer a un claustro con peso
+            if tipo == TIPO_DE_USUARIO::VOTANTE { self.validar_claustro(&eleccion, id)?; }
+            // un referendum no tiene candidatos
+            if tipo == TIPO_DE_USUARIO::CANDIDATO && eleccion.es_referendum() { return Err(Error::MODO_VOTACION_INCORRECTO); }
+
+            //Validar que un usuario que ya ha sido rechazado en la misma eleccion no intente volver a ponerse como pendiente
+            if self.es_usuario_rechazado_en_eleccion(eleccion_id, id) {  return Err(Error::INSCRIPCION_RECHAZADA); }
+            if self.es_usuario_pendiente_en_eleccion(eleccion_id, id) { return Err(Error::INSCRIPCION_PENDIENTE); }
+            if self.es_votante(eleccion_id, id) { return Err(Error::YA_ES_VOTANTE);}
+            if self.es_candidato(eleccion_id, id) {return Err(Error::YA_ES_CANDIDATO);}
+
+            self.encolar_inscripcion(eleccion_id, id, tipo.clone())?;
+            self.env().emit_event(InscripcionSolicitada { eleccion_id, usuario: id, tipo });
+            Ok(())
+        }
+
+
+        /// Utilizado por un candidato de la elección, antes de que empiece la votación.
+        /// Se retira de la elección: deja de aparecer en los resultados y no puede recibir votos.
+        /// Su id de candidato no se reutiliza. Puede volver a inscribirse mientras la elección siga abierta.
+        /// Si integra una lista, primero debe eliminarse la lista para no romper la paridad del resto.
+        #[ink(message)]
+        pub fn retirar_candidatura(&mut self, eleccion_id:u64) -> Result<()>
+        {
+            let usuario = self.env().caller();
+            let mut eleccion = self.validar_eleccion_sin_iniciar(eleccion_id)?;
+            let candidato_id = self.quitar_candidato(&mut eleccion, usuario)?;
+            self.guardar_eleccion(&eleccion);
+            self.env().emit_event(CandidaturaRetirada { eleccion_id, usuario, candidato_id });
+            Ok(())
+        }
+
+        /// Utilizado por un votante de la elección, antes de que empiece la votación.
+        /// Se da de baja del padrón. Puede volver a inscribirse mientras la elección siga abierta.
+        #[ink(message)]
+        pub fn darse_de_baja_como_votante(&mut self, eleccion_id:u64) -> Result<()>
+        {
+            let usuario = self.env().caller();
+            let mut eleccion = self.validar_eleccion_sin_iniciar(eleccion_id)?;
+            self.quitar_votante(&mut eleccion, usuario)?;
+            self.guardar_eleccion(&eleccion);
+            self.env().emit_event(VotanteDadoDeBaja { eleccion_id, usuario });
+            Ok(())
+        }
+
+        /// Utilizado por los usuarios registrados en el sistema y que están en la elección ingresada.
+        /// Se utiliza para poder obtener información de algún candidato en específico.
+        /// Devuelve el nombre y apellido, que son públicos para quien se presenta como candidato, y su plataforma.
+        /// Las IDs de los candidatos van de 1 a N, salvo las de los candidatos que se retiraron.
+        #[ink(message)]
+        pub fn obtener_informacion_candidato(&self, eleccion_id: u64, candidato_id: u64) -> Result<InformacionCandidato> {
+            let eleccion_elegida = self.obtener_eleccion_por_id(eleccion_id)
+                .ok_or(Error::ELECCION_NO_EXISTE)?;
+
+            let candidato_elegido = self.obtener_informacion_candidato_en_eleccion(&eleccion_elegida, candidato_id)
+                .ok_or(Error::CANDIDATO_NO_EXISTE)?;
+
+            let usuario_id = candidato_elegido.usuario_id;
+            let usuario = self.obtener_usuario_por_id(usuario_id)
+                .ok_or(Error::USUARIO_NO_ENCONTRADO)?;
+
+            Ok(InformacionCandidato {
+                candidato_id,
+                usuario_id,
+                nombre: usuario.nombre,
+                apellido: usuario.apellido,
+                plataforma: self.plataformas.get((eleccion_id, candidato_id)),
+            })
+        }
+
+        /// Utilizado por un candidato de la elección, antes de que empiece la votación.
+        /// Publica o reemplaza su plataforma: propuesta, documento externo y partido.
+        #[ink(message)]
+        pub fn publicar_plataforma(&mut self, eleccion_id:u64, plataforma:PlataformaCandidato) -> Result<()>
+        {
+            plataforma.validar()?;
+            let eleccion = self.validar_eleccion_sin_iniciar(eleccion_id)?;
+            let candidato_id = self.candidato_por_usuario.get((eleccion.id, self.env().caller())).ok_or(Error::NO_ES_CANDIDATO)?;
+            self.plataformas.insert((eleccion_id, candidato_id), &plataforma);
+            self.env().emit_event(PlataformaPublicada { eleccion_id, candidato_id });
+            Ok(())
+        }
+
+        /// Utilizado por los usuarios registrados en el sistema y que están en la elección como votantes.
+        /// Si el usuario ya emitió su voto, no puede volver a votar en la misma elección.
+        /// Si el usuario no es votante, no puede votar.
+        /// Si el periodo de la votación no comenzó o terminó, no puede votar.
+        /// Solo para elecciones en modo DIRECTO.
+        /// Si otros votantes delegaron en él, el voto cuenta también por ellos (ver `delegar_voto`).
+        #[ink(message)]
+        pub fn votar_a_candidato(&mut self, eleccion_id:u64, candidato_id:u64) -> Result<()>
+        {
+            let (mut eleccion, id) = self.validar_votante(eleccion_id)?;
+            self.votar_candidato(&mut eleccion, id, candidato_id)?;
+            self.guardar_eleccion(&eleccion);
+            Ok(())
+        }
+
+        /// Utilizado por los votantes de una elección en modo DIRECTO, con cualquier sistema de escrutinio.
+        /// Emite un voto en blanco. En modo SECRETO se vota en blanco comprometiendo el id VOTO_EN_BLANCO.
+        #[ink(message)]
+        pub fn votar_en_blanco(&mut self, eleccion_id:u64) -> Result<()>
+        {
+            let (mut eleccion, id) = self.validar_votante(eleccion_id)?;
+            if eleccion.es_secreta() { return Err(Error::MODO_VOTACION_INCORRECTO); }
+            self.sumar_voto_a_claustro(&eleccion, id, VOTO_EN_BLANCO)?;
+            let votos = self.registrar_voto_con_delegaciones(&mut eleccion, id)?;
+            eleccion.votos_en_blanco = eleccion.votos_en_blanco.checked_add(votos).ok_or(Error::OVERFLOW)?;
+            self.guardar_eleccion(&eleccion);
+            Ok(())
+        }
+
+        /// Utilizado por los votantes de una elección en modo DIRECTO, con cualquier sistema de escrutinio.
+        /// Emite un voto nulo. En modo SECRETO cuenta como nulo el compromiso revelado con un id que no es de ningún candidato.
+        #[ink(message)]
+        pub fn votar_nulo(&mut self, eleccion_id:u64) -> Result<()>
+        {
+            let (mut eleccion, id) = self.validar_votante(eleccion_id)?;
+            if eleccion.es_secreta() { return Err(Error::MODO_VOTACION_INCORRECTO); }
+            let votos = self.registrar_voto_con_delegaciones(&mut eleccion, id)?;
+            eleccion.votos_nulos = eleccion.votos_nulos.checked_add(votos).ok_or(Error::OVERFLOW)?;
+            self.guardar_eleccion(&eleccion);
+            Ok(())
+        }
+
+        /// Devuelve los votos en blanco y nulos de la elección, disponibles cuando lo está el conteo de los candidatos.
+        #[ink(message)]
+        pub fn obtener_votos_blancos_y_nulos(&self, eleccion_id:u64) -> Result<VotosBlancosYNulos>
+        {
+            let eleccion = self.obtener_eleccion_por_id(eleccion_id).ok_or(Error::ELECCION_NO_EXISTE)?;
+            if !eleccion.conteo_disponible(self.env().block_timestamp()) { return Err(Error::ELECCION_NO_FINALIZADA); }
+            Ok(VotosBlancosYNulos { votos_en_blanco: eleccion.votos_en_blanco, votos_nulos: eleccion.votos_nulos })
+        }
+
+    // ====================================================================
+    // ===================Eleccion: delegacion de votos====================
+    // ====================================================================
+
+        /// Utilizado por los votantes de una elección que permite delegación, durante la votación.
+        /// Delega el voto en otro votante que todavía no votó, reemplazando la delegación anterior si la había.
+        /// Las delegaciones son transitivas: si el delegado también delega, el voto sigue la cadena.
+        /// Cuando el último de la cadena vota, su voto cuenta también por todos los que delegaron en él.
+        /// Si ese votante nunca vota, los votos delegados no se cuentan.
+        #[ink(message)]
+        pub fn delegar_voto(&mut self, eleccion_id:u64, delegado:AccountId) -> Result<()>
+        {
+            let (eleccion, id) = self.validar_votante(eleccion_id)?;
+            if !eleccion.configuracion.permite_delegacion { return Err(Error::MODO_VOTACION_INCORRECTO); }
+            if delegado == id { return Err(Error::DELEGACION_CIRCULAR); }
+            let votante_delegado = self.votantes.get((eleccion_id, delegado)).ok_or(Error::DELEGADO_NO_ES_VOTANTE)?;
+            if votante_delegado.voto_emitido { return Err(Error::DELEGADO_YA_VOTO); }
+
+            // Las delegaciones vigentes no tienen ciclos, así que seguir la cadena desde el delegado siempre termina.
+            let mut actual = delegado;
+            while let Some(siguiente) = self.delegaciones.get((eleccion_id, actual)) {
+                if siguiente == id { return Err(Error::DELEGACION_CIRCULAR); }
+                actual = siguiente;
+            }
+
+            if let Some(anterior) = self.anular_delegacion(eleccion_id, id) {
+                self.env().emit_event(DelegacionRevocada { eleccion_id, votante: id, delegado: anterior });
+            }
+            self.delegaciones.insert((eleccion_id, id), &delegado);
+            let mut delegantes = self.delegantes.get((eleccion_id, delegado)).unwrap_or_default();
+            delegantes.push(id);
+            self.delegantes.insert((eleccion_id, delegado), &delegantes);
+            self.env().emit_event(VotoDelegado { eleccion_id, votante: id, delegado });
+            Ok(())
+        }
+
+        /// Utilizado por un votante que delegó su voto, mientras su voto no haya sido emitido por la cadena de delegados.
+        /// Quienes delegaron en él siguen delegando en él.
+        #[ink(message)]
+        pub fn revocar_delegacion(&mut self, eleccion_id:u64) -> Result<()>
+        {
+            let (_, id) = self.validar_votante(eleccion_id)?;
+            let delegado = self.anular_delegacion(eleccion_id, id).ok_or(Error::NO_HAY_DELEGACION)?;
+            self.env().emit_event(DelegacionRevocada { eleccion_id, votante: id, delegado });
+            Ok(())
+        }
+
+        /// Devuelve en quién delegó su voto el votante, si lo hizo.
+        /// Las delegaciones que se usaron al votar el delegado se siguen informando.
+        #[ink(message)]
+        pub fn obtener_delegacion(&self, eleccion_id:u64, votante:AccountId) -> Option<AccountId>
+        {
+            self.delegaciones.get((eleccion_id, votante))
+        }
+
+        /// Devuelve los votantes que emitieron votos delegados y cuántos, en el orden del padrón.
+        /// Disponible cuando lo está el conteo de la elección. Revisa hasta `cantidad` votantes del padrón a partir
+        /// de la posición `desde`, paginados como `obtener_padron_eleccion`, y devuelve los que recibieron votos.
+        #[ink(message)]
+        pub fn obtener_votos_delegados(&self, eleccion_id:u64, desde:u64, cantidad:u64) -> Result<Vec<VotosDelegados>>
+        {
+            let eleccion = self.obtener_eleccion_por_id(eleccion_id).ok_or(Error::ELECCION_NO_EXISTE)?;
+            if !eleccion.conteo_disponible(self.env().block_timestamp()) { return Err(Error::ELECCION_NO_FINALIZADA); }
+            Ok(self.pagina_de_padron(&eleccion, desde, cantidad).into_iter()
+                .filter_map(|votante| self.votos_delegados.get((eleccion_id, votante.usuario_id))
+                    .map(|votos| VotosDelegados { delegado: votante.usuario_id, votos }))
+                .collect())
+        }
+
+    // ====================================================================
+    // ===================Eleccion: voto preferencial======================
+    // ====================================================================
+
+        /// Utilizado por los votantes de una elección PREFERENCIAL o SCHULZE.
+        /// Recibe los ids de los candidatos ordenados de mayor a menor preferencia.
+        /// No es necesario ordenar a todos los candidatos. En PREFERENCIAL la boleta se agota cuando se eliminan
+        /// todos los que incluye; en SCHULZE los candidatos incluidos se prefieren a los que no lo están.
+        /// Para las consultas de candidatos, el voto se suma a la primera preferencia.
+        #[ink(message)]
+        pub fn votar_preferencias(&mut self, eleccion_id:u64, preferencias:Boleta) -> Result<()>
+        {
+            let (mut eleccion, id) = self.validar_votante(eleccion_id)?;
+            if !eleccion.es_preferencial() && !eleccion.es_schulze() { return Err(Error::MODO_VOTACION_INCORRECTO); }
+            self.validar_boleta_preferencial(&eleccion, &preferencias)?;
+
+            self.sumar_voto_a_candidato(&eleccion, preferencias[0], 1)?;
+            self.registrar_voto_emitido(&mut eleccion, id)?;
+            if eleccion.es_schulze() {
+                self.sumar_boleta_a_matriz(&eleccion, &preferencias)?;
+            } else {
+                self.boletas.insert((eleccion_id, eleccion.cantidad_boletas), &preferencias);
+            }
+            eleccion.cantidad_boletas = eleccion.cantidad_boletas.checked_add(1).ok_or(Error::OVERFLOW)?;
+            self.guardar_eleccion(&eleccion);
+            Ok(())
+        }
+
+        /// Devuelve las rondas de la segunda vuelta instantánea de una elección PREFERENCIAL ya escrutada,
+        /// en el orden en que se realizaron.
+        #[ink(message)]
+        pub fn obtener_rondas_escrutinio(&self, eleccion_id:u64) -> Result<Vec<RondaEscrutinio>>
+        {
+            let eleccion = self.obtener_eleccion_por_id(eleccion_id).ok_or(Error::ELECCION_NO_EXISTE)?;
+            if !eleccion.es_preferencial() { return Err(Error::MODO_VOTACION_INCORRECTO); }
+            if !eleccion.escrutada { return Err(Error::ELECCION_NO_FINALIZADA); }
+            Ok(self.rondas_escrutinio.get(eleccion_id).unwrap_or_default())
+        }
+
+    // ====================================================================
+    // ===================Eleccion: voto por aprobacion====================
+    // ====================================================================
+
+        /// Utilizado por los votantes de una elección APROBACION.
+        /// Recibe los ids de los candidatos que el votante aprueba, en cualquier orden, sin repetir
+        /// y sin superar el máximo de aprobaciones de la elección. Cada candidato aprobado suma un voto.
+        #[ink(message)]
+        pub fn votar_aprobaciones(&mut self, eleccion_id:u64, aprobados:Vec<u64>) -> Result<()>
+        {
+            let (mut eleccion, id) = self.validar_votante(eleccion_id)?;
+            let SISTEMA_ESCRUTINIO::APROBACION { maximo_aprobaciones } = eleccion.configuracion.sistema_escrutinio else {
+                return Err(Error::MODO_VOTACION_INCORRECTO);
+            };
+            if aprobados.is_empty() { return Err(Error::BOLETA_VACIA); }
+            if maximo_aprobaciones.is_some_and(|maximo| aprobados.len() as u64 > maximo) { return Err(Error::DEMASIADAS_APROBACIONES); }
+            for (posicion, candidato_id) in aprobados.iter().enumerate() {
+                if self.obtener_informacion_candidato_en_eleccion(&eleccion, *candidato_id).is_none() { return Err(Error::CANDIDATO_NO_EXISTE); }
+                if aprobados[..posicion].contains(candidato_id) { return Err(Error::CANDIDATO_REPETIDO); }
+            }
+
+            for candidato_id in aprobados.iter() {
+                self.sumar_voto_a_candidato(&eleccion, *candidato_id, 1)?;
+            }
+            self.registrar_voto_emitido(&mut eleccion, id)?;
+            eleccion.cantidad_boletas = eleccion.cantidad_boletas.checked_add(1).ok_or(Error::OVERFLOW)?;
+            self.guardar_eleccion(&eleccion);
+            Ok(())
+        }
+
+        /// Devuelve las aprobaciones de cada candidato de una elección APROBACION ya escrutada, ordenadas por id.
+        #[ink(message)]
+        pub fn obtener_resultado_aprobacion(&self, eleccion_id:u64) -> Result<Vec<ResultadoAprobacion>>
+        {
+            let eleccion = self.obtener_eleccion_por_id(eleccion_id).ok_or(Error::ELECCION_NO_EXISTE)?;
+            if !eleccion.es_aprobacion() { return Err(Error::MODO_VOTACION_INCORRECTO); }
+            if !eleccion.escrutada { return Err(Error::ELECCION_NO_FINALIZADA); }
+            Ok(self.resultados_aprobacion.get(eleccion_id).unwrap_or_default())
+        }
+
+    // ====================================================================
+    // ===================Eleccion: metodo de Schulze======================
+    // ====================================================================
+
+        /// Devuelve la matriz de preferencias por pares de una elección SCHULZE, disponible cuando lo está el conteo.
+        #[ink(message)]
+        pub fn obtener_matriz_preferencias(&self, eleccion_id:u64) -> Result<MatrizPares>
+        {
+            let eleccion = self.obtener_eleccion_por_id(eleccion_id).ok_or(Error::ELECCION_NO_EXISTE)?;
+            if !eleccion.es_schulze() { return Err(Error::MODO_VOTACION_INCORRECTO); }
+            if !eleccion.conteo_disponible(self.env().block_timestamp()) { return Err(Error::ELECCION_NO_FINALIZADA); }
+            Ok(self.obtener_matriz_pares(&eleccion))
+        }
+
+        /// Devuelve la fuerza de los caminos más fuertes entre cada par de candidatos de una elección SCHULZE ya escrutada.
+        #[ink(message)]
+        pub fn obtener_caminos_mas_fuertes(&self, eleccion_id:u64) -> Result<MatrizPares>
+        {
+            let eleccion = self.obtener_eleccion_por_id(eleccion_id).ok_or(Error::ELECCION_NO_EXISTE)?;
+            if !eleccion.es_schulze() { return Err(Error::MODO_VOTACION_INCORRECTO); }
+            if !eleccion.escrutada { return Err(Error::ELECCION_NO_FINALIZADA); }
+            Ok(self.caminos_mas_fuertes.get(eleccion_id).unwrap_or_default())
+        }
+
+    // ====================================================================
+    // ===================Eleccion: referendum=============================
+    // ====================================================================
+
+        /// Devuelve la pregunta, las opciones y la mayoría requerida de un referéndum.
+        #[ink(message)]
+        pub fn obtener_propuesta(&self, eleccion_id:u64) -> Result<Propuesta>
+        {
+            let eleccion = self.obtener_eleccion_por_id(eleccion_id).ok_or(Error::ELECCION_NO_EXISTE)?;
+            if !eleccion.es_referendum() { return Err(Error::MODO_VOTACION_INCORRECTO); }
+            self.propuestas.get(eleccion_id).ok_or(Error::ELECCION_NO_EXISTE)
+        }
+
+        /// Utilizado por los votantes de un referéndum.
+        /// Elige una opción de la propuesta por su id, de 1 a N. También se puede votar en blanco o nulo.
+        #[ink(message)]
+        pub fn votar_opcion(&mut self, eleccion_id:u64, opcion_id:u64) -> Result<()>
+        {
+            let (mut eleccion, id) = self.validar_votante(eleccion_id)?;
+            if !eleccion.es_referendum() { return Err(Error::MODO_VOTACION_INCORRECTO); }
+            let propuesta = self.propuestas.get(eleccion_id).ok_or(Error::ELECCION_NO_EXISTE)?;
+            if opcion_id == 0 || opcion_id > propuesta.opciones.len() as u64 { return Err(Error::OPCION_NO_EXISTE); }
+
+            let votos = self.votos_por_opcion.get((eleccion_id, opcion_id)).unwrap_or(0);
+            self.votos_por_opcion.insert((eleccion_id, opcion_id), &votos.checked_add(1).ok_or(Error::OVERFLOW)?);
+            self.registrar_voto_emitido(&mut eleccion, id)?;
+            self.guardar_eleccion(&eleccion);
+            Ok(())
+        }
+
+        /// Devuelve los votos de cada opción de un referéndum ya escrutado y si la propuesta fue aprobada.
+        #[ink(message)]
+        pub fn obtener_resultado_referendum(&self, eleccion_id:u64) -> Result<ResultadoReferendum>
+        {
+            let eleccion = self.obtener_eleccion_por_id(eleccion_id).ok_or(Error::ELECCION_NO_EXISTE)?;
+            if !eleccion.es_referendum() { return Err(Error::MODO_VOTACION_INCORRECTO); }
+            if !eleccion.escrutada { return Err(Error::ELECCION_NO_FINALIZADA); }
+            self.resultados_referendum.get(eleccion_id).ok_or(Error::ELECCION_NO_FINALIZADA)
+        }
+
+    // ====================================================================
+    // ===================Eleccion: listas y reparto de bancas=============
+    // ====================================================================
+
+        /// Utilizado por un administrador o un oficial electoral de una elección DHONDT, antes de que empiece la votación.
+        /// Crea una lista con candidatos ya aceptados en la elección, en el orden en que ocuparán las bancas.
+        /// Un candidato solo puede integrar una lista. Todos deben tener su género declarado y la lista debe
+        /// cumplir la ley de paridad, intercalando géneros. Devuelve el id de la lista creada.
+        #[ink(message)]
+        pub fn crear_lista(&mut self, eleccion_id:u64, numero:u64, nombre:String, candidatos:Vec<u64>) -> Result<u64>
+        {
+            self.verificar_permiso(PERMISO::GESTIONAR_ELECCION(eleccion_id))?;
+            Self::validar_longitud_texto(&nombre)?;
+            let mut eleccion = self.validar_eleccion_sin_iniciar(eleccion_id)?;
+            if !eleccion.es_dhondt() { return Err(Error::MODO_VOTACION_INCORRECTO); }
+            if candidatos.is_empty() { return Err(Error::LISTA_VACIA); }
+            if self.obtener_listas_de_eleccion(&eleccion).iter().any(|lista| lista.numero == numero) {
+                return Err(Error::NUMERO_DE_LISTA_REPETIDO);
+            }
+
+            let mut miembros = Vec::new();
+            for (posicion, candidato_id) in candidatos.iter().enumerate() {
+                let candidato = self.obtener_informacion_candidato_en_eleccion(&eleccion, *candidato_id)
+                    .ok_or(Error::CANDIDATO_NO_EXISTE)?;
+                if candidatos[..posicion].contains(candidato_id) { return Err(Error::CANDIDATO_REPETIDO); }
+                if self.lista_de_candidato.contains((eleccion_id, *candidato_id)) { return Err(Error::CANDIDATO_YA_EN_LISTA); }
+                let genero = self.generos.get(candidato.usuario_id).ok_or(Error::GENERO_NO_DECLARADO)?;
+                miembros.push(MiembroLista { candidato_id: *candidato_id, genero });
+            }
+            if !Lista::cumple_paridad(&miembros) { return Err(Error::PARIDAD_INCUMPLIDA); }
+
+            let lista_id = eleccion.cantidad_listas.checked_add(1).ok_or(Error::OVERFLOW)?;
+            eleccion.cantidad_listas = lista_id;
+            for candidato_id in candidatos.iter() {
+                self.lista_de_candidato.insert((eleccion_id, *candidato_id), &lista_id);
+            }
+            self.listas.insert((eleccion_id, lista_id), &Lista { id: lista_id, numero, nombre, candidatos: miembros });
+            self.guardar_eleccion(&eleccion);
+            self.env().emit_event(ListaCreada { eleccion_id, lista_id, numero });
+            Ok(lista_id)
+        }
+
+        /// Utilizado por un administrador o un oficial electoral de la elección, antes de que empiece la votación.
+        /// Elimina una lista. Sus candidatos quedan libres para integrar otra y su id no se reutiliza.
+        #[ink(message)]
+        pub fn eliminar_lista(&mut self, eleccion_id:u64, lista_id:u64) -> Result<()>
+        {
+            self.verificar_permiso(PERMISO::GESTIONAR_ELECCION(eleccion_id))?;
+            self.validar_eleccion_sin_iniciar(eleccion_id)?;
+            let lista = self.listas.get((eleccion_id, lista_id)).ok_or(Error::LISTA_NO_EXISTE)?;
+            for miembro in lista.candidatos.iter() {
+                self.lista_de_candidato.remove((eleccion_id, miembro.candidato_id));
+            }
+            self.listas.remove((eleccion_id, lista_id));
+            self.env().emit_event(ListaEliminada { eleccion_id, lista_id });
+            Ok(())
+        }
+
+        /// Devuelve las listas de una elección DHONDT ordenadas por id.
+        #[ink(message)]
+        pub fn obtener_listas(&self, eleccion_id:u64) -> Result<Vec<Lista>>
+        {
+            let eleccion = self.obtener_eleccion_por_id(eleccion_id).ok_or(Error::ELECCION_NO_EXISTE)?;
+            if !eleccion.es_dhondt() { return Err(Error::MODO_VOTACION_INCORRECTO); }
+            Ok(self.obtener_listas_de_eleccion(&eleccion))
+        }
+
+        /// Utilizado por los votantes de una elección DHONDT.
+        /// El voto es para la lista completa; las bancas se asignan a sus candidatos en el orden de la lista.
+        #[ink(message)]
+        pub fn votar_lista(&mut self, eleccion_id:u64, lista_id:u64) -> Result<()>
+        {
+            let (mut eleccion, id) = self.validar_votante(eleccion_id)?;
+            if !eleccion.es_dhondt() { return Err(Error::MODO_VOTACION_INCORRECTO); }
+            if !self.listas.contains((eleccion_id, lista_id)) { return Err(Error::LISTA_NO_EXISTE); }
+
+            let votos = self.votos_por_lista.get((eleccion_id, lista_id)).unwrap_or(0);
+            self.votos_por_lista.insert((eleccion_id, lista_id), &votos.checked_add(1).ok_or(Error::OVERFLOW)?);
+            self.registrar_voto_emitido(&mut eleccion, id)?;
+            self.guardar_eleccion(&eleccion);
+            Ok(())
+        }
+
+        /// Devuelve el reparto de bancas de una elección DHONDT ya escrutada, con los votos de cada lista
+        /// y los candidatos electos.
+        #[ink(message)]
+        pub fn obtener_reparto_bancas(&self, eleccion_id:u64) -> Result<Vec<ResultadoLista>>
+        {
+            let eleccion = self.obtener_eleccion_por_id(eleccion_id).ok_or(Error::ELECCION_NO_EXISTE)?;
+            if !eleccion.es_dhondt() { return Err(Error::MODO_VOTACION_INCORRECTO); }
+            if !eleccion.escrutada { return Err(Error::ELECCION_NO_FINALIZADA); }
+            Ok(self.reparto_bancas.get(eleccion_id).unwrap_or_default())
+        }
+
+    // ====================================================================
+    // ===================Eleccion: ponderacion por claustros==============
+    // ====================================================================
+
+        /// Devuelve los votos de cada claustro de una elección ponderada, disponibles cuando lo está el conteo.
+        #[ink(message)]
+        pub fn obtener_conteo_por_claustro(&self, eleccion_id:u64) -> Result<Vec<ConteoClaustro>>
+        {
+            let eleccion = self.obtener_eleccion_por_id(eleccion_id).ok_or(Error::ELECCION_NO_EXISTE)?;
+            if !eleccion.es_ponderada() { return Err(Error::MODO_VOTACION_INCORRECTO); }
+            if !eleccion.conteo_disponible(self.env().block_timestamp()) { return Err(Error::ELECCION_NO_FINALIZADA); }
+            self.conteo_por_claustro(&eleccion)
+        }
+
+        /// Devuelve el resultado ponderado de cada candidato de una elección ponderada ya escrutada, ordenado por id.
+        #[ink(message)]
+        pub fn obtener_resultado_ponderado(&self, eleccion_id:u64) -> Result<Vec<ResultadoPonderado>>
+        {
+            let eleccion = self.obtener_eleccion_por_id(eleccion_id).ok_or(Error::ELECCION_NO_EXISTE)?;
+            if !eleccion.es_ponderada() { return Err(Error::MODO_VOTACION_INCORRECTO); }
+            if !eleccion.escrutada { return Err(Error::ELECCION_NO_FINALIZADA); }
+            Ok(self.resultados_ponderados.get(eleccion_id).unwrap_or_default())
+        }
+
+    // ====================================================================
+    // ===================Eleccion: voto secreto===========================
+    // ====================================================================
+
+        /// Utilizado por los votantes de una elección en modo SECRETO durante la votación.
+        /// Registra el compromiso del voto, calculado como blake2x256 de la codificación SCALE de
+        /// (eleccion_id, votante, candidato_id, sal), donde votante es la cuenta que vota.
+        /// La sal debe ser aleatoria y guardarse hasta revelar el voto; sin ella el voto no se podrá contar.
+        #[ink(message)]
+        pub fn comprometer_voto(&mut self, eleccion_id:u64, compromiso:Hash) -> Result<()>
+        {
+            let (mut eleccion, id) = self.validar_votante(eleccion_id)?;
+            if !eleccion.es_secreta() { return Err(Error::MODO_VOTACION_INCORRECTO); }
+
+            self.registrar_voto_emitido(&mut eleccion, id)?;
+            self.compromisos.insert((eleccion_id, id), &compromiso);
+            eleccion.compromisos_emitidos = eleccion.compromisos_emitidos.checked_add(1).ok_or(Error::OVERFLOW)?;
+            self.guardar_eleccion(&eleccion);
+            Ok(())
+        }
+
+        /// Utilizado por los votantes de una elección en modo SECRETO, una vez pasada la fecha final
+        /// y antes de que termine la ventana de revelación.
+        /// Si el candidato y la sal coinciden con el compromiso registrado, el voto se suma al candidato.
+        /// Con VOTO_EN_BLANCO cuenta como voto en blanco, y con un id que no es de ningún candidato, como nulo.
+        #[ink(message)]
+        pub fn revelar_voto(&mut self, eleccion_id:u64, candidato_id:u64, sal:[u8; 32]) -> Result<()>
+        {
+            let id = self.env().caller();
+            let mut eleccion = self.actualizar_eleccion(eleccion_id)?;
+            if !eleccion.es_secreta() { return Err(Error::MODO_VOTACION_INCORRECTO); }
+
+            let block_timestamp = self.env().block_timestamp();
+            if block_timestamp <= eleccion.fecha_final { return Err(Error::REVELACION_NO_INICIADA); }
+            if block_timestamp > eleccion.fin_revelacion() { return Err(Error::REVELACION_FINALIZADA); }
+
+            let compromiso = self.compromisos.get((eleccion_id, id)).ok_or(Error::SIN_COMPROMISO)?;
+            if Self::hash_compromiso(eleccion_id, id, candidato_id, &sal) != compromiso { return Err(Error::COMPROMISO_INVALIDO); }
+
+            if candidato_id == VOTO_EN_BLANCO {
+                self.sumar_voto_a_claustro(&eleccion, id, VOTO_EN_BLANCO)?;
+                eleccion.votos_en_blanco = eleccion.votos_en_blanco.checked_add(1).ok_or(Error::OVERFLOW)?;
+            } else if self.obtener_informacion_candidato_en_eleccion(&eleccion, candidato_id).is_none() {
+                eleccion.votos_nulos = eleccion.votos_nulos.checked_add(1).ok_or(Error::OVERFLOW)?;
+            } else {
+                self.sumar_voto_a_candidato(&eleccion, candidato_id, 1)?;
+                self.sumar_voto_a_claustro(&eleccion, id, candidato_id)?;
+            }
+            self.compromisos.remove((eleccion_id, id));
+            eleccion.compromisos_revelados = eleccion.compromisos_revelados.checked_add(1).ok_or(Error::OVERFLOW)?;
+            self.guardar_eleccion(&eleccion);
+            self.env().emit_event(VotoRevelado { eleccion_id, votante: id });
+            Ok(())
+        }
+
+        /// Calcula el compromiso del voto secreto de quien llama en la elección indicada.
+        /// La consulta no queda registrada en la cadena, pero quien opere el nodo podría verla:
+        /// de ser posible conviene calcular el compromiso fuera de la cadena con el mismo algoritmo.
+        #[ink(message)]
+        pub fn calcular_compromiso(&self, eleccion_id:u64, candidato_id:u64, sal:[u8; 32]) -> Hash
+        {
+            Self::hash_compromiso(eleccion_id, self.env().caller(), candidato_id, &sal)
+        }
+
+        /// Devuelve los votantes que registraron un compromiso y todavía no lo revelaron. Revisa hasta `cantidad`
+        /// votantes del padrón a partir de la posición `desde`, paginados como `obtener_padron_eleccion`.
+        #[ink(message)]
+        pub fn obtener_votantes_sin_revelar(&self, eleccion_id:u64, desde:u64, cantidad:u64) -> Result<Vec<AccountId>>
+        {
+            let eleccion = self.obtener_eleccion_por_id(eleccion_id).ok_or(Error::ELECCION_NO_EXISTE)?;
+            Ok(self.pagina_de_padron(&eleccion, desde, cantidad).into_iter()
+                .map(|votante| votante.usuario_id)
+                .filter(|votante_id| self.compromisos.contains((eleccion_id, *votante_id)))
+                .collect())
+        }
+
+    // ====================================================================
+    // ====================================================================
+    // ====================================================================
+
+
+
+        /// Utilizado por el administrador.
+        /// Propone a otra cuenta como administrador. El traspaso se hace en dos pasos: la cuenta propuesta debe
+        /// aceptarlo con `aceptar_administrador` dentro de `plazo_aceptacion` milisegundos y, si `espera` es mayor
+        /// a cero, completarlo con `completar_traspaso_administrador` una vez pasada la espera.
+        /// Hasta que se complete, el administrador actual conserva el control y puede cancelarlo.
+        #[ink(message)]
+        pub fn proponer_administrador(&mut self, propuesto:AccountId, plazo_aceptacion:u64, espera:u64) -> Result<()>
+        {
+            self.verificar_permiso(PERMISO::ADMINISTRAR_SISTEMA)?;
+            if self.traspaso_administrador.is_some() { return Err(Error::TRASPASO_EN_CURSO); }
+            if self.administrador == Some(propuesto) || plazo_aceptacion == 0 { return Err(Error::TRASPASO_INVALIDO); }
+
+            let vencimiento = self.env().block_timestamp().checked_add(plazo_aceptacion).ok_or(Error::OVERFLOW)?;
+            self.traspaso_administrador = Some(TraspasoAdministrador {
+                propuesto,
+                vencimiento,
+                espera,
+                efectivo_desde: None,
+            });
+            self.env().emit_event(TraspasoAdministradorPropuesto { propuesto, vencimiento, espera });
+            Ok(())
+        }
+
+        /// Utilizado por la cuenta propuesta como administrador, antes del vencimiento de la propuesta.
+        /// Sin espera el traspaso se completa en el momento.
+        #[ink(message)]
+        pub fn aceptar_administrador(&mut self) -> Result<()>
+        {
+            let caller = self.env().caller();
+            let block_timestamp = self.env().block_timestamp();
+            let mut traspaso = self.traspaso_administrador.clone().ok_or(Error::NO_HAY_TRASPASO)?;
+            if traspaso.propuesto != caller { return Err(Error::NO_ES_ADMINISTRADOR_PROPUESTO); }
+            if traspaso.efectivo_desde.is_some() { return Err(Error::TRASPASO_YA_ACEPTADO); }
+            if block_timestamp > traspaso.vencimiento { return Err(Error::TRASPASO_VENCIDO); }
+
+            if traspaso.espera == 0 { return self.completar_traspaso(caller); }
+            let efectivo_desde = block_timestamp.checked_add(traspaso.espera).ok_or(Error::OVERFLOW)?;
+            traspaso.efectivo_desde = Some(efectivo_desde);
+            self.traspaso_administrador = Some(traspaso);
+            self.env().emit_event(TraspasoAdministradorAceptado { propuesto: caller, efectivo_desde });
+            Ok(())
+        }
+
+        /// Utilizado por la cuenta propuesta como administrador, una vez aceptado el traspaso y pasada la espera.
+        #[ink(message)]
+        pub fn completar_traspaso_administrador(&mut self) -> Result<()>
+        {
+            let caller = self.env().caller();
+            let traspaso = self.traspaso_administrador.clone().ok_or(Error::NO_HAY_TRASPASO)?;
+            if traspaso.propuesto != caller { return Err(Error::NO_ES_ADMINISTRADOR_PROPUESTO); }
+            let efectivo_desde = traspaso.efectivo_desde.ok_or(Error::TRASPASO_NO_ACEPTADO)?;
+            if self.env().block_timestamp() < efectivo_desde { return Err(Error::ESPERA_EN_CURSO); }
+            self.completar_traspaso(caller)
+        }
+
+        /// Utilizado por el administrador.
+        /// Cancela el traspaso propuesto, haya sido aceptado o no, mientras no se haya completado.
+        #[ink(message)]
+        pub fn cancelar_traspaso_administrador(&mut self) -> Result<()>
+        {
+            self.verificar_permiso(PERMISO::ADMINISTRAR_SISTEMA)?;
+            let traspaso = self.traspaso_administrador.take().ok_or(Error::NO_HAY_TRASPASO)?;
+            self.env().emit_event(TraspasoAdministradorCancelado { propuesto: traspaso.propuesto });
+            Ok(())
+        }
+
+        /// Utilizado por el administrador.
+        /// Renuncia a la administración sin sucesor. Es irreversible: nadie podrá volver a usar los mensajes
+        /// exclusivos del administrador. Los roles ya otorgados se conservan.
+        /// Para confirmar, `cuenta_administrador` debe ser la cuenta del administrador actual.
+        #[ink(message)]
+        pub fn renunciar_administracion(&mut self, cuenta_administrador:AccountId) -> Result<()>
+        {
+            self.verificar_permiso(PERMISO::ADMINISTRAR_SISTEMA)?;
+            if self.administrador != Some(cuenta_administrador) { return Err(Error::NO_ES_ADMINISTRADOR); }
+            if self.traspaso_administrador.is_some() { return Err(Error::TRASPASO_EN_CURSO); }
+
+            self.administrador = None;
+            self.env().emit_event(AdministracionRenunciada { anterior: cuenta_administrador });
+            Ok(())
+        }
+
+        /// Devuelve el administrador actual, o None si renunció.
+        #[ink(message)]
+        pub fn obtener_administrador(&self) -> Option<AccountId>
+        {
+            self.administrador
+        }
+
+        /// Devuelve el traspaso de administrador en curso, si lo hay.
+        #[ink(message)]
+        pub fn obtener_traspaso_administrador(&self) -> Option<TraspasoAdministrador>
+        {
+            self.traspaso_administrador.clone()
+        }
+
+    // ====================================================================
+    // ===================Rechazos y apelaciones===========================
+    // ====================================================================
+
+        /// Utilizado por un usuario rechazado en el sistema (`eleccion_id` None) o en una elección.
+        /// Presenta una apelación con el texto que la fundamenta. Se admite una sola apelación por rechazo.
+        #[ink(message)]
+        pub fn apelar_rechazo(&mut self, eleccion_id:Option<u64>, texto:String) -> Result<()>
+        {
+            let usuario = self.env().caller();
+            let rechazado = match eleccion_id {
+                None => self.es_usuario_rechazado(usuario),
+                Some(eleccion_id) => self.es_usuario_rechazado_en_eleccion(eleccion_id, usuario),
+            };
+            if !rechazado { return Err(Error::NO_ESTA_RECHAZADO); }
+            Self::validar_longitud_texto(&texto)?;
+
+            let mut historial = self.historial_rechazos.get(usuario).unwrap_or_default();
+            let posicion = Self::ultimo_rechazo(&historial, eleccion_id).ok_or(Error::NO_ESTA_RECHAZADO)?;
+            let rechazo = historial.get_mut(posicion).ok_or(Error::NO_ESTA_RECHAZADO)?;
+            if rechazo.apelacion.is_some() { return Err(Error::APELACION_YA_PRESENTADA); }
+
+            rechazo.apelacion = Some(Apelacion {
+                texto,
+                fecha: self.env().block_timestamp(),
+                resolucion: None,
+                resuelta_por: None,
+                fecha_resolucion: None,
+            });
+            self.historial_rechazos.insert(usuario, &historial);
+            let posicion = self.rango_apelaciones.agregar()?;
+            self.apelaciones_pendientes.insert(posicion, &ApelacionPendiente { usuario, eleccion_id });
+            self.posicion_de_apelacion.insert((usuario, eleccion_id), &posicion);
+            self.env().emit_event(ApelacionPresentada { usuario, eleccion_id });
+            Ok(())
+        }
+
+        /// Utilizado por un administrador, un registrador para las apelaciones del sistema
+        /// o un oficial electoral para las de su elección.
+        /// Resuelve la apelación pendiente del usuario. Para reincorporarlo a una elección esta debe estar ABIERTA.
+        #[ink(message)]
+        pub fn resolver_apelacion(&mut self, usuario:AccountId, eleccion_id:Option<u64>, resolucion:RESOLUCION_APELACION) -> Result<()>
+        {
+            self.verificar_permiso(Self::permiso_de_ambito(eleccion_id))?;
+
+            let posicion_pendiente = self.posicion_de_apelacion.get((usuario, eleccion_id)).ok_or(Error::NO_HAY_APELACION_PENDIENTE)?;
+            let mut historial = self.historial_rechazos.get(usuario).unwrap_or_default();
+            let posicion = Self::ultimo_rechazo(&historial, eleccion_id).ok_or(Error::NO_HAY_APELACION_PENDIENTE)?;
+            let rechazo = historial.get_mut(posicion).ok_or(Error::NO_HAY_APELACION_PENDIENTE)?;
+            let tipo = rechazo.tipo.clone();
+            let apelacion = rechazo.apelacion.as_mut().ok_or(Error::NO_HAY_APELACION_PENDIENTE)?;
+
+            if resolucion != RESOLUCION_APELACION::DENEGAR {
+                match eleccion_id {
+                    None => {
+                        self.reincorporar_usuario(usuario)?;
+                        if resolucion == RESOLUCION_APELACION::ACEPTAR { self.procesar_usuario(usuario, None)?; }
+                    },
+                    Some(eleccion_id) => {
+                        let mut eleccion = self.validar_estado_eleccion_para_inscripciones(eleccion_id)?;
+                        self.reincorporar_inscripcion(eleccion_id, usuario, tipo)?;
+                        if resolucion == RESOLUCION_APELACION::ACEPTAR {
+                            self.procesar_usuario_pendiente_en_eleccion(&mut eleccion, usuario, None)?;
+                        }
+                    },
+                }
+            }
+
+            apelacion.resolucion = Some(resolucion.clone());
+            apelacion.resuelta_por = Some(self.env().caller());
+            apelacion.fecha_resolucion = Some(self.env().block_timestamp());
+            self.historial_rechazos.insert(usuario, &historial);
+            self.posicion_de_apelacion.remove((usuario, eleccion_id));
+            Self::quitar_de_lista(&mut self.apelaciones_pendientes, |posicion| posicion, &mut self.rango_apelaciones, posicion_pendiente)?;
+            self.env().emit_event(ApelacionResuelta { usuario, eleccion_id, resolucion });
+            Ok(())
+        }
+
+        /// Devuelve las apelaciones sin resolver, paginadas como `obtener_usuarios`, en el orden en que se presentaron.
+        #[ink(message)]
+        pub fn obtener_apelaciones_pendientes(&self, desde:u64, cantidad:u64) -> Vec<ApelacionPendiente>
+        {
+            Self::pagina_de_lista(&self.apelaciones_pendientes, |posicion| posicion, self.rango_apelaciones, desde, cantidad)
+        }
+        /// Cantidad de apelaciones sin resolver.
+        #[ink(message)]
+        pub fn obtener_cantidad_apelaciones_pendientes(&self) -> u64
+        {
+            self.rango_apelaciones.cantidad()
+        }
+
+        /// Utilizado por el propio usuario o por quien puede aceptarlo o rechazarlo en ese ámbito.
+        /// Devuelve el último rechazo del usuario en el sistema (`eleccion_id` None) o en una elección, con su apelación.
+        #[ink(message)]
+        pub fn obtener_rechazo(&self, usuario:AccountId, eleccion_id:Option<u64>) -> Result<Rechazo>
+        {
+            if !self.puede_leer_datos_de(usuario, &[PERMISO::LEER_DATOS_PERSONALES, Self::permiso_de_ambito(eleccion_id)]) {
+                return Err(Error::SIN_PERMISO);
+            }
+            let historial = self.historial_rechazos.get(usuario).unwrap_or_default();
+            let posicion = Self::ultimo_rechazo(&historial, eleccion_id).ok_or(Error::NO_ESTA_RECHAZADO)?;
+            historial.get(posicion).cloned().ok_or(Error::NO_ESTA_RECHAZADO)
+        }
+
+        /// Utilizado por el propio usuario, un auditor o un registrador.
+        /// Devuelve todos los rechazos del usuario, en el sistema y en las elecciones, en orden cronológico.
+        #[ink(message)]
+        pub fn obtener_historial_rechazos(&self, usuario:AccountId) -> Result<Vec<Rechazo>>
+        {
+            if !self.puede_leer_datos_de(usuario, &[PERMISO::LEER_DATOS_PERSONALES, PERMISO::APROBAR_USUARIOS]) {
+                return Err(Error::SIN_PERMISO);
+            }
+            Ok(self.historial_rechazos.get(usuario).unwrap_or_default())
+        }
+
+    // ====================================================================
+    // ===================Roles============================================
+    // ====================================================================
+
+        /// Utilizado por el administrador.
+        /// Otorga un rol a una cuenta. Los oficiales electorales se otorgan para una elección existente.
+        #[ink(message)]
+        pub fn otorgar_rol(&mut self, cuenta:AccountId, rol:ROL) -> Result<()>
+        {
+            self.verificar_permiso(PERMISO::ADMINISTRAR_SISTEMA)?;
+            if let ROL::OFICIAL_ELECTORAL(eleccion_id) = rol {
+                if !self.existe_eleccion(eleccion_id) { return Err(Error::ELECCION_NO_EXISTE); }
+            }
+
+            let mut roles = self.roles.get(cuenta).unwrap_or_default();
+            if roles.contains(&rol) { return Err(Error::ROL_YA_ASIGNADO); }
+            roles.push(rol.clone());
+            self.roles.insert(cuenta, &roles);
+            self.env().emit_event(RolOtorgado { cuenta, rol });
+            Ok(())
+        }
+
+        /// Utilizado por el administrador.
+        /// Quita un rol a una cuenta.
+        #[ink(message)]
+        pub fn revocar_rol(&mut self, cuenta:AccountId, rol:ROL) -> Result<()>
+        {
+            self.verificar_permiso(PERMISO::ADMINISTRAR_SISTEMA)?;
+
+            let mut roles = self.roles.get(cuenta).unwrap_or_default();
+            let posicion = roles.iter().position(|asignado| *asignado == rol).ok_or(Error::ROL_NO_ASIGNADO)?;
+            roles.remove(posicion);
+            if roles.is_empty() { self.roles.remove(cuenta); } else { self.roles.insert(cuenta, &roles); }
+            self.env().emit_event(RolRevocado { cuenta, rol });
+            Ok(())
+        }
+
+        /// Devuelve los roles otorgados a una cuenta.
+        #[ink(message)]
+        pub fn obtener_roles(&self, cuenta:AccountId) -> Vec<ROL>
+        {
+            self.roles.get(cuenta).unwrap_or_default()
+        }
+
+        /// Indica si la cuenta puede realizar las operaciones que exigen el permiso.
+        #[ink(message)]
+        pub fn tiene_permiso(&self, cuenta:AccountId, permiso:PERMISO) -> bool
+        {
+            self.cuenta_tiene_permiso(cuenta, &permiso)
+        }
+
+    // ====================================================================
+    // ===================Reportes=========================================
+    // ====================================================================
+
+        /// Utilizado por un administrador.
+        /// Autoriza al contrato de reportes a consultar la API de reportes. Con None se revoca el acceso.
+        #[ink(message)]
+        pub fn establecer_contrato_reportes(&mut self, contrato:Option<AccountId>) -> Result<()>
+        {
+            self.verificar_permiso(PERMISO::ADMINISTRAR_SISTEMA)?;
+            self.contrato_reportes = contrato;
+            self.env().emit_event(ContratoReportesCambiado { contrato });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn obtener_contrato_reportes(&self) -> Option<AccountId>
+        {
+            self.contrato_reportes
+        }
+
+        /// Utilizado por el contrato de reportes.
+        /// Devuelve los datos generales de la elección, con la cantidad de votantes del padrón y de votos emitidos.
+        #[ink(message)]
+        pub fn obtener_resumen_eleccion(&self, eleccion_id:u64) -> Result<ResumenEleccion>
+        {
+            if !self.es_contrato_reportes() { return Err(Error::NO_ES_CONTRATO_REPORTES); }
+            let eleccion = self.obtener_eleccion_por_id(eleccion_id).ok_or(Error::ELECCION_NO_EXISTE)?;
+            Ok(ResumenEleccion::from(&eleccion))
+        }
+
+        /// Utilizado por el contrato de reportes.
+        /// Devuelve hasta `cantidad` votantes del padrón (como máximo MAXIMO_POR_PAGINA) a partir de la posición `desde`.
+        /// El padrón no tiene posiciones vacías: se recorre completo avanzando `desde` hasta `cantidad_votantes`.
+        #[ink(message)]
+        pub fn obtener_padron_eleccion(&self, eleccion_id:u64, desde:u64, cantidad:u64) -> Result<Vec<EstadoVotante>>
+        {
+            if !self.es_contrato_reportes() { return Err(Error::NO_ES_CONTRATO_REPORTES); }
+            let eleccion = self.obtener_eleccion_por_id(eleccion_id).ok_or(Error::ELECCION_NO_EXISTE)?;
+            Ok(self.pagina_de_padron(&eleccion, desde, cantidad).into_iter().map(EstadoVotante::from).collect())
+        }
+
+        /// Utilizado por el contrato de reportes.
+        /// Devuelve los candidatos con sus votos. No disponible mientras la votación está en curso.
+        #[ink(message)]
+        pub fn obtener_conteo_eleccion(&self, eleccion_id:u64) -> Result<Vec<CandidatoConVotos>>
+        {
+            if !self.es_contrato_reportes() { return Err(Error::NO_ES_CONTRATO_REPORTES); }
+            let eleccion = self.obtener_eleccion_por_id(eleccion_id).ok_or(Error::ELECCION_NO_EXISTE)?;
+            if !eleccion.conteo_disponible(self.env().block_timestamp()) { return Err(Error::ELECCION_NO_FINALIZADA); }
+            Ok(self.obtener_candidatos_de_eleccion(&eleccion).into_iter().map(CandidatoConVotos::from).collect())
+        }
+
+    // ====================================================================
+    // ===================Migracion========================================
+    // ====================================================================
+
+        /// Utilizado por un administrador mientras la migración está en curso.
+        /// Importa un lote de usuarios del almacenamiento anterior con el estado indicado, convertidos con
+        /// `UsuarioImportado::desde_legado`. Los usuarios pendientes deben importarse en el orden de la cola original.
+        #[ink(message)]
+        pub fn importar_usuarios_legado(&mut self, usuarios:Vec<UsuarioImportado>, estado:ESTADO_USUARIO) -> Result<()>
+        {
+            self.verificar_permiso(PERMISO::ADMINISTRAR_SISTEMA)?;
+            if !self.migracion_en_curso { return Err(Error::MIGRACION_FINALIZADA); }
+
+            for usuario in usuarios {
+                if self.usuarios.contains(usuario.id) { return Err(Error::USUARIO_YA_REGISTRADO); }
+                let dni_hash = usuario.dni_hash;
+                if let Some(cuenta_original) = self.usuario_por_dni.get(dni_hash) {
+                    let posicion = self.rango_posibles_duplicados.agregar()?;
+                    self.posibles_duplicados.insert(posicion, &PosibleDuplicado { cuenta: usuario.id, cuenta_original });
+                }
+                if estado == ESTADO_USUARIO::RECHAZADO {
+                    self.registrar_rechazo(usuario.id, None, None, MotivoRechazo::default());
+                }
+                self.agregar_usuario(Usuario {
+                    id: usuario.id,
+                    nombre: usuario.nombre,
+                    apellido: usuario.apellido,
+                    dni_hash,
+                    dni_cifrado: None,
+                    estado: estado.clone(),
+                })?;
+            }
+            Ok(())
+        }
+
+        /// Utilizado por un administrador mientras la migración está en curso.
+        /// Importa una elección del almacenamiento anterior conservando su id, padrón, candidatos y votos.
+        /// Las elecciones deben importarse en orden: la primera con id 1, la siguiente con id 2, etc.
+        #[ink(message)]
+        pub fn importar_eleccion_legada(&mut self, eleccion_legada:EleccionLegada) -> Result<()>
+        {
+            self.verificar_permiso(PERMISO::ADMINISTRAR_SISTEMA)?;
+            if !self.migracion_en_curso { return Err(Error::MIGRACION_FINALIZADA); }
+
+            let eleccion_id = self.cantidad_elecciones.checked_add(1).ok_or(Error::OVERFLOW)?;
+            if eleccion_legada.id != eleccion_id { return Err(Error::ELECCION_LEGADA_FUERA_DE_ORDEN); }
+
+            let mut eleccion = Eleccion {
+                id: eleccion_id,
+                estado: eleccion_legada.estado,
+                fecha_inicio: eleccion_legada.fecha_inicio,
+                fecha_final: eleccion_legada.fecha_final,
+                cantidad_candidatos: 0,
+                candidatos_retirados: 0,
+                cantidad_votantes: 0,
+                votos_emitidos: 0,
+                configuracion: ConfiguracionEleccion::default(),
+                compromisos_emitidos: 0,
+                compromisos_revelados: 0,
+                cantidad_boletas: 0,
+                votos_en_blanco: 0,
+                votos_nulos: 0,
+                cantidad_listas: 0,
+                escrutada: false,
+                ganador: None,
+                eleccion_anterior: None,
+                segunda_vuelta: None,
+            };
+            // Los candidatos vienen ordenados por id, así que al agregarlos en orden conservan el mismo id.
+            for candidato in eleccion_legada.candidatos {
+                self.agregar_candidato(&mut eleccion, candidato.usuario_id, candidato.votos_totales)?;
+            }
+            for votante in eleccion_legada.votantes {
+                if votante.voto_emitido {
+                    eleccion.votos_emitidos = eleccion.votos_emitidos.checked_add(1).ok_or(Error::OVERFLOW)?;
+                }
+                self.agregar_votante(&mut eleccion, Votante {
+                    usuario_id: votante.usuario_id,
+                    voto_emitido: votante.voto_emitido,
+                    claustro: None,
+                })?;
+            }
+            for usuario in eleccion_legada.usuarios_rechazados {
+                self.inscripciones.insert((eleccion_id, usuario), &ESTADO_INSCRIPCION::RECHAZADA);
+                self.registrar_rechazo(usuario, Some(eleccion_id), None, MotivoRechazo::default());
+            }
+            for (usuario, tipo) in eleccion_legada.usuarios_pendientes {
+                self.encolar_inscripcion(eleccion_id, usuario, tipo)?;
+            }
+
+            self.guardar_eleccion(&eleccion);
+            self.cantidad_elecciones = eleccion_id;
+            Ok(())
+        }
+
+        /// Utilizado por un administrador.
+        /// Cierra la migración. A partir de este momento no se pueden importar más datos y se pueden crear elecciones.
+        #[ink(message)]
+        pub fn finalizar_migracion(&mut self) -> Result<()>
+        {
+            self.verificar_permiso(PERMISO::ADMINISTRAR_SISTEMA)?;
+            if !self.migracion_en_curso { return Err(Error::MIGRACION_FINALIZADA); }
+            self.migracion_en_curso = false;
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        type Entorno = ink::env::DefaultEnvironment;
+
+        fn cuenta(numero:u8) -> AccountId {
+            AccountId::from([numero; 32])
+        }
+        fn administrador() -> AccountId {
+            ink::env::test::default_accounts::<Entorno>().alice
+        }
+        fn como(cuenta:AccountId) {
+            ink::env::test::set_caller::<Entorno>(cuenta);
+        }
+
+        /// Clave del registro con la que la aplicación de registro calcula los hashes de DNI.
+        const CLAVE_DNI:[u8; 32] = [7; 32];
+        fn dni(numero:&str) -> Hash {
+            hash_dni(&CLAVE_DNI, numero).unwrap()
+        }
+
+        /// Contrato creado por el administrador con `cantidad` usuarios registrados en ese orden y pendientes
+        /// de aprobación. Los usuarios son cuenta(10), cuenta(11), etc., y el llamador queda en el administrador.
+        fn contrato_con_pendientes(cantidad:u8) -> (TrabajoFinal, Vec<AccountId>) {
+            como(administrador());
+            let mut contrato = TrabajoFinal::new();
+            contrato.activar_registro().unwrap();
+            let usuarios:Vec<AccountId> = (0..cantidad).map(|numero| cuenta(numero.checked_add(10).unwrap())).collect();
+            for (numero, usuario) in usuarios.iter().enumerate() {
+                como(*usuario);
+                contrato.registrarse(String::from("Nombre"), String::from("Apellido"), dni(&format!("30000{:03}", numero)), None).unwrap();
+            }
+            como(administrador());
+            (contrato, usuarios)
+        }
+        /// Como `contrato_con_pendientes`, pero con todos los usuarios ya aceptados.
+        fn contrato_con_usuarios(cantidad:u8) -> (TrabajoFinal, Vec<AccountId>) {
+            let (mut contrato, usuarios) = contrato_con_pendientes(cantidad);
+            for _ in usuarios.iter() { contrato.procesar_siguiente_usuario_pendiente(true).unwrap(); }
+            (contrato, usuarios)
+        }
+
+        /// Fechas de inicio y fin de las elecciones de prueba, 01-01-2030 10:00 y 01-01-2031 10:00, en milisegundos.
+        const INICIO:u64 = 1_893_492_000_000;
+        const FIN:u64 = 1_925_028_000_000;
+
+        fn en_fecha(block_timestamp:u64) {
+            ink::env::test::set_block_timestamp::<Entorno>(block_timestamp);
+        }
+        /// Pasa la fecha final de la elección y la finaliza como administrador, lo que realiza el escrutinio.
+        fn finalizar(contrato:&mut TrabajoFinal, eleccion_id:u64) {
+            en_fecha(FIN.checked_add(1).unwrap());
+            como(administrador());
+            contrato.finalizar_eleccion(eleccion_id).unwrap();
+        }
+
+        /// Crea y abre una elección entre INICIO y FIN, inscribe a los candidatos y a los votantes y los acepta
+        /// en ese orden, así que los candidatos reciben los ids 1, 2, etc. El llamador queda en el administrador.
+        fn eleccion_abierta(contrato:&mut TrabajoFinal, configuracion:ConfiguracionEleccion, candidatos:&[AccountId], votantes:&[AccountId]) -> u64 {
+            como(administrador());
+            let eleccion_id = contrato.crear_eleccion(String::from("01-01-2030 10:00"), String::from("01-01-2031 10:00"), configuracion).unwrap();
+            contrato.abrir_eleccion(eleccion_id).unwrap();
+            let inscripciones = candidatos.iter().map(|usuario| (usuario, TIPO_DE_USUARIO::CANDIDATO))
+                .chain(votantes.iter().map(|usuario| (usuario, TIPO_DE_USUARIO::VOTANTE)));
+            for (usuario, tipo) in inscripciones {
+                como(*usuario);
+                contrato.inscribir_usuario_en_eleccion(eleccion_id, tipo).unwrap();
+            }
+            como(administrador());
+            for _ in candidatos.iter().chain(votantes.iter()) {
+                contrato.procesar_usuarios_en_una_eleccion(eleccion_id, true).unwrap();
+            }
+            eleccion_id
+        }
+
+        /// Contrato con una elección del sistema indicado y `candidatos` candidatos con ids de 1 a `candidatos`.
+        fn eleccion_con_candidatos(sistema_escrutinio:SISTEMA_ESCRUTINIO, candidatos:u8) -> (TrabajoFinal, Eleccion) {
+            let mut contrato = TrabajoFinal::new();
+            let configuracion = ConfiguracionEleccion { sistema_escrutinio, ..Default::default() };
+            let mut eleccion = contrato.nueva_eleccion(0, 0, configuracion, None).unwrap();
+            for numero in 1..=candidatos {
+                contrato.agregar_candidato(&mut eleccion, cuenta(numero), 0).unwrap();
+            }
+            contrato.guardar_eleccion(&eleccion);
+            (contrato, eleccion)
+        }
+
+        /// Elección del sistema indicado con `candidatos` candidatos en la que cada grupo de `cantidad` votantes
+        /// emite la misma boleta de preferencias. Se devuelve todavía sin finalizar.
+        fn eleccion_votada(sistema_escrutinio:SISTEMA_ESCRUTINIO, candidatos:usize, boletas:&[(usize, &[u64])]) -> (TrabajoFinal, u64) {
+            let votantes = boletas.iter().try_fold(0usize, |total, (cantidad, _)| total.checked_add(*cantidad)).unwrap();
+            let (mut contrato, usuarios) = contrato_con_usuarios(u8::try_from(candidatos.checked_add(votantes).unwrap()).unwrap());
+            let configuracion = ConfiguracionEleccion { sistema_escrutinio, ..Default::default() };
+            let eleccion_id = eleccion_abierta(&mut contrato, configuracion, &usuarios[..candidatos], &usuarios[candidatos..]);
+            en_fecha(INICIO);
+            let mut votantes = usuarios[candidatos..].iter();
+            for (cantidad, preferencias) in boletas {
+                for votante in votantes.by_ref().take(*cantidad) {
+                    como(*votante);
+                    contrato.votar_preferencias(eleccion_id, preferencias.to_vec()).unwrap();
+                }
+            }
+            (contrato, eleccion_id)
+        }
+        /// Como `eleccion_votada`, pero se devuelve ya finalizada y escrutada.
+        fn eleccion_con_boletas(sistema_escrutinio:SISTEMA_ESCRUTINIO, candidatos:usize, boletas:&[(usize, &[u64])]) -> (TrabajoFinal, u64) {
+            let (mut contrato, eleccion_id) = eleccion_votada(sistema_escrutinio, candidatos, boletas);
+            finalizar(&mut contrato, eleccion_id);
+            (contrato, eleccion_id)
+        }
+        fn ganador(contrato:&TrabajoFinal, eleccion_id:u64) -> Option<u64> {
+            contrato.obtener_datos_eleccion_por_id(eleccion_id).unwrap().ganador
+        }
+
+        /// Elección DHONDT finalizada en la que cada lista recibe `votos` votos y tiene `integrantes` candidatos de
+        /// géneros intercalados. Los candidatos se inscriben lista por lista, así que la primera lista tiene los ids
+        /// 1 a `integrantes`, la segunda los siguientes, etc.
+        fn eleccion_dhondt(bancas:u64, umbral:u64, listas:&[(usize, usize)]) -> (TrabajoFinal, u64) {
+            let candidatos = listas.iter().try_fold(0usize, |total, (_, integrantes)| total.checked_add(*integrantes)).unwrap();
+            let votantes = listas.iter().try_fold(0usize, |total, (votos, _)| total.checked_add(*votos)).unwrap();
+            let (mut contrato, usuarios) = contrato_con_usuarios(u8::try_from(candidatos.checked_add(votantes).unwrap()).unwrap());
+            let mut candidatos_por_lista = Vec::new();
+            let mut siguiente = usuarios[..candidatos].iter();
+            for (_, integrantes) in listas {
+                for (posicion, candidato) in siguiente.by_ref().take(*integrantes).enumerate() {
+                    como(*candidato);
+                    contrato.declarar_genero(if posicion % 2 == 0 { GENERO::FEMENINO } else { GENERO::MASCULINO }).unwrap();
+                }
+                let primero = candidatos_por_lista.iter().map(|ids:&Vec<u64>| ids.len()).sum::<usize>();
+                candidatos_por_lista.push((1..=*integrantes).map(|posicion| u64::try_from(primero.checked_add(posicion).unwrap()).unwrap()).collect());
+            }
+
+            let configuracion = ConfiguracionEleccion { sistema_escrutinio: SISTEMA_ESCRUTINIO::DHONDT { bancas, umbral }, ..Default::default() };
+            let eleccion_id = eleccion_abierta(&mut contrato, configuracion, &usuarios[..candidatos], &usuarios[candidatos..]);
+            let mut listas_ids = Vec::new();
+            for (numero, integrantes) in candidatos_por_lista.into_iter().enumerate() {
+                let numero = u64::try_from(numero.checked_add(1).unwrap()).unwrap();
+                listas_ids.push(contrato.crear_lista(eleccion_id, numero, format!("Lista {}", numero), integrantes).unwrap());
+            }
+            en_fecha(INICIO);
+            let mut votantes = usuarios[candidatos..].iter();
+            for ((votos, _), lista_id) in listas.iter().zip(listas_ids) {
+                for votante in votantes.by_ref().take(*votos) {
+                    como(*votante);
+                    contrato.votar_lista(eleccion_id, lista_id).unwrap();
+                }
+            }
+            finalizar(&mut contrato, eleccion_id);
+            (contrato, eleccion_id)
+        }
+
+        fn bancas_por_lista(resultados:&[ResultadoLista]) -> Vec<u64> {
+            resultados.iter().map(|resultado| resultado.bancas).collect()
+        }
+
+        fn votos_de_ronda(ronda:&RondaEscrutinio) -> Vec<(u64, u64)> {
+            ronda.conteo.iter().map(|conteo| (conteo.candidato_id, conteo.votos)).collect()
+        }
+
+        // ===================================================================================================
+        // =========================DNI=======================================================================
+        // ===================================================================================================
+
+        #[test]
+        fn normalizar_dni_valida_el_formato() {
+            assert_eq!(normalizar_dni("1234567"), Ok("1234567"));
+            assert_eq!(normalizar_dni("01234567"), Ok("1234567"));
+            assert_eq!(normalizar_dni("30000000"), Ok("30000000"));
+            assert_eq!(normalizar_dni("00000001"), Ok("1"));
+            for invalido in ["", "0000000", "00000000", "123456", "123456789", "12.345.678", "1234567a", " 1234567", "+1234567"] {
+                assert_eq!(normalizar_dni(invalido), Err(Error::DNI_INVALIDO), "{:?}", invalido);
+            }
+        }
+
+        /// El hash identifica al DNI normalizado y depende de la clave del registro.
+        #[test]
+        fn hash_dni_coincide_solo_para_el_mismo_dni() {
+            assert_eq!(hash_dni(&CLAVE_DNI, "1234567"), hash_dni(&CLAVE_DNI, "01234567"));
+            assert_ne!(hash_dni(&CLAVE_DNI, "1234567"), hash_dni(&CLAVE_DNI, "12345670"));
+            assert_ne!(hash_dni(&CLAVE_DNI, "1234567"), hash_dni(&[8; 32], "1234567"));
+            assert_eq!(hash_dni(&CLAVE_DNI, "0000000"), Err(Error::DNI_INVALIDO));
+        }
+
+        /// Dos cuentas que envían el hash del mismo DNI, escrito con o sin ceros a la izquierda, chocan.
+        #[ink::test]
+        fn registrarse_con_el_hash_de_un_dni_ya_registrado_falla() {
+            let (mut contrato, _) = contrato_con_pendientes(0);
+            como(cuenta(10));
+            assert_eq!(contrato.registrarse(String::from("Nombre"), String::from("Apellido"), Hash::default(), None), Err(Error::DNI_INVALIDO));
+            contrato.registrarse(String::from("Nombre"), String::from("Apellido"), dni("1234567"), None).unwrap();
+            como(cuenta(11));
+            assert_eq!(contrato.registrarse(String::from("Nombre"), String::from("Apellido"), dni("01234567"), None), Err(Error::DNI_YA_REGISTRADO));
+            contrato.registrarse(String::from("Nombre"), String::from("Apellido"), dni("12345670"), None).unwrap();
+
+            como(administrador());
+            assert_eq!(contrato.obtener_usuario_por_dni(dni("01234567")), Ok(cuenta(10)));
+            assert_eq!(contrato.obtener_usuario_por_dni(dni("7654321")), Err(Error::USUARIO_NO_ENCONTRADO));
+        }
+
+        /// Un DNI queda tomado por la primera cuenta que lo registró, esté pendiente, aceptada o rechazada.
+        #[ink::test]
+        fn el_dni_queda_tomado_en_cualquier_estado() {
+            let (mut contrato, usuarios) = contrato_con_pendientes(3);
+            contrato.procesar_siguiente_usuario_pendiente(true).unwrap();
+            contrato.rechazar_usuario_pendiente(usuarios[1], MotivoRechazo::default()).unwrap();
+            assert_eq!(contrato.obtener_cantidad_usuarios(ESTADO_USUARIO::PENDIENTE), 1);
+
+            for (numero, usuario) in usuarios.iter().enumerate() {
+                let dni_tomado = dni(&format!("30000{:03}", numero));
+                como(cuenta(20));
+                assert_eq!(contrato.registrarse(String::from("Otro"), String::from("Usuario"), dni_tomado, None), Err(Error::DNI_YA_REGISTRADO));
+                como(administrador());
+                assert_eq!(contrato.obtener_usuario_por_dni(dni_tomado), Ok(*usuario));
+            }
+            como(cuenta(20));
+            contrato.registrarse(String::from("Otro"), String::from("Usuario"), dni("30000003"), None).unwrap();
+        }
+
+        /// Los ceros a la izquierda no cuentan, tengan la cantidad que tengan.
+        #[ink::test]
+        fn los_ceros_a_la_izquierda_no_evitan_el_duplicado() {
+            let (mut contrato, _) = contrato_con_pendientes(0);
+            como(cuenta(10));
+            contrato.registrarse(String::from("Nombre"), String::from("Apellido"), dni("0012345"), None).unwrap();
+            como(cuenta(11));
+            assert_eq!(contrato.registrarse(String::from("Nombre"), String::from("Apellido"), dni("00012345"), None), Err(Error::DNI_YA_REGISTRADO));
+            assert_eq!(contrato.obtener_usuario_por_dni(dni("00012345")), Err(Error::SIN_PERMISO));
+            como(administrador());
+            assert_eq!(contrato.obtener_usuario_por_dni(dni("00012345")), Ok(cuenta(10)));
+        }
+
+        /// Los duplicados que ya existían en el almacenamiento anterior se importan igual y quedan listados,
+        /// y después nadie puede volver a registrar esos DNI.
+        #[ink::test]
+        fn la_migracion_lista_los_dni_duplicados() {
+            como(administrador());
+            let mut contrato = TrabajoFinal::nuevo_para_migracion();
+            let legado = |numero:u8, dni:&str| UsuarioImportado::desde_legado(UsuarioLegado {
+                id: cuenta(numero),
+                nombre: String::from("Nombre"),
+                apellido: String::from("Apellido"),
+                dni: String::from(dni),
+            }, &CLAVE_DNI);
+            contrato.importar_usuarios_legado(vec![legado(10, "1234567"), legado(11, "12.345.678")], ESTADO_USUARIO::ACEPTADO).unwrap();
+            contrato.importar_usuarios_legado(vec![legado(12, "01234567"), legado(13, "12.345.678"), legado(14, "12345678")], ESTADO_USUARIO::PENDIENTE).unwrap();
+            contrato.finalizar_migracion().unwrap();
+
+            assert_eq!(contrato.obtener_posibles_duplicados(0, 10), Ok(vec![
+                PosibleDuplicado { cuenta: cuenta(12), cuenta_original: cuenta(10) },
+                PosibleDuplicado { cuenta: cuenta(13), cuenta_original: cuenta(11) },
+            ]));
+            contrato.activar_registro().unwrap();
+            como(cuenta(20));
+            assert_eq!(contrato.registrarse(String::from("Nombre"), String::from("Apellido"), dni("1234567"), None), Err(Error::DNI_YA_REGISTRADO));
+            assert_eq!(contrato.registrarse(String::from("Nombre"), String::from("Apellido"), dni("12345678"), None), Err(Error::DNI_YA_REGISTRADO));
+        }
+
+        // ===================================================================================================
+        // =========================colas de aprobación=======================================================
+        // ===================================================================================================
+
+        /// Aprobar a un usuario del medio de la cola deja su posición vacía: los demás conservan el orden
+        /// de llegada y las páginas la saltean.
+        #[ink::test]
+        fn aprobar_fuera_de_turno_conserva_el_orden_de_la_cola() {
+            let (mut contrato, usuarios) = contrato_con_pendientes(4);
+            contrato.procesar_usuario_pendiente(usuarios[1], true).unwrap();
+            assert_eq!(contrato.obtener_usuarios_pendientes(0, 10), vec![usuarios[0], usuarios[2], usuarios[3]]);
+            assert_eq!(contrato.obtener_cantidad_usuarios(ESTADO_USUARIO::PENDIENTE), 3);
+            assert_eq!(contrato.obtener_usuarios_pendientes(0, 2), vec![usuarios[0]]);
+            assert_eq!(contrato.obtener_usuarios_pendientes(2, 2), vec![usuarios[2], usuarios[3]]);
+
+            contrato.procesar_usuario_pendiente(usuarios[2], false).unwrap();
+            assert_eq!(contrato.obtener_informacion_siguiente_usuario_pendiente().unwrap().id, usuarios[0]);
+            assert_eq!(contrato.procesar_siguiente_usuario_pendiente(true), Ok(usuarios[0]));
+            // Al salir el primero, el inicio saltea las posiciones vacías hasta el siguiente.
+            assert_eq!(contrato.obtener_usuarios_pendientes(0, 1), vec![usuarios[3]]);
+            assert_eq!(contrato.procesar_siguiente_usuario_pendiente(true), Ok(usuarios[3]));
+            assert_eq!(contrato.procesar_siguiente_usuario_pendiente(true), Err(Error::NO_HAY_USUARIOS_PENDIENTES));
+            assert_eq!(contrato.obtener_usuarios(0, 10), vec![usuarios[1], usuarios[0], usuarios[3]]);
+        }
+
+        /// Un usuario que vuelve a la cola, por ejemplo al aceptarse una apelación, queda al final.
+        #[ink::test]
+        fn los_nuevos_pendientes_van_al_final_de_la_cola() {
+            let (mut contrato, usuarios) = contrato_con_pendientes(3);
+            contrato.procesar_usuario_pendiente(usuarios[0], false).unwrap();
+            como(usuarios[0]);
+            contrato.apelar_rechazo(None, String::from("Mi DNI es correcto")).unwrap();
+            como(administrador());
+            contrato.resolver_apelacion(usuarios[0], None, RESOLUCION_APELACION::VOLVER_A_PENDIENTE).unwrap();
+            assert_eq!(contrato.obtener_usuarios_pendientes(0, 10), vec![usuarios[1], usuarios[2], usuarios[0]]);
+        }
+
+        /// Un lote procesa a cada usuario por separado: los que no están pendientes fallan sin afectar al resto.
+        #[ink::test]
+        fn el_lote_del_sistema_informa_el_resultado_de_cada_usuario() {
+            let (mut contrato, usuarios) = contrato_con_pendientes(4);
+            contrato.procesar_usuario_pendiente(usuarios[2], true).unwrap();
+            como(usuarios[1]);
+            assert_eq!(contrato.procesar_lote_usuarios_pendientes(vec![usuarios[3]], true), Err(Error::SIN_PERMISO));
+
+            como(administrador());
+            let resultados = contrato.procesar_lote_usuarios_pendientes(vec![usuarios[3], usuarios[2], cuenta(99), usuarios[0]], false).unwrap();
+            assert_eq!(resultados, vec![
+                ResultadoProcesamiento { usuario: usuarios[3], resultado: Ok(()) },
+                ResultadoProcesamiento { usuario: usuarios[2], resultado: Err(Error::USUARIO_NO_PENDIENTE) },
+                ResultadoProcesamiento { usuario: cuenta(99), resultado: Err(Error::USUARIO_NO_PENDIENTE) },
+                ResultadoProcesamiento { usuario: usuarios[0], resultado: Ok(()) },
+            ]);
+            assert_eq!(contrato.obtener_usuarios_pendientes(0, 10), vec![usuarios[1]]);
+            assert_eq!(contrato.obtener_usuarios(0, 10), vec![usuarios[2]]);
+            assert_eq!(contrato.obtener_usuarios_rechazados(0, 10), vec![usuarios[3], usuarios[0]]);
+            assert_eq!(contrato.obtener_historial_rechazos(usuarios[0]).unwrap().len(), 1);
+        }
+
+        /// En una elección se puede procesar cualquier solicitud de la cola, sola o en lote. En el lote, un candidato
+        /// que ya no puede aceptarse porque empezó la votación queda pendiente sin impedir que entren los votantes.
+        #[ink::test]
+        fn el_lote_de_una_eleccion_informa_el_resultado_de_cada_usuario() {
+            let (mut contrato, usuarios) = contrato_con_usuarios(5);
+            let eleccion_id = eleccion_abierta(&mut contrato, ConfiguracionEleccion::default(), &[], &[]);
+            for (usuario, tipo) in usuarios.iter().zip([TIPO_DE_USUARIO::CANDIDATO, TIPO_DE_USUARIO::VOTANTE, TIPO_DE_USUARIO::VOTANTE, TIPO_DE_USUARIO::VOTANTE, TIPO_DE_USUARIO::VOTANTE]) {
+                como(*usuario);
+                contrato.inscribir_usuario_en_eleccion(eleccion_id, tipo).unwrap();
+            }
+            como(administrador());
+            contrato.procesar_usuario_en_una_eleccion(eleccion_id, usuarios[2], false).unwrap();
+            assert_eq!(contrato.procesar_usuario_en_una_eleccion(eleccion_id, usuarios[2], true), Err(Error::INSCRIPCION_NO_PENDIENTE));
+            assert_eq!(contrato.obtener_siguiente_usuario_pendiente_en_una_eleccion(eleccion_id).unwrap().usuario_id, usuarios[0]);
+
+            en_fecha(INICIO);
+            let resultados = contrato.procesar_lote_en_una_eleccion(eleccion_id, vec![usuarios[0], usuarios[3], usuarios[3], usuarios[1]], true).unwrap();
+            assert_eq!(resultados, vec![
+                ResultadoProcesamiento { usuario: usuarios[0], resultado: Err(Error::VOTACION_INICIADA) },
+                ResultadoProcesamiento { usuario: usuarios[3], resultado: Ok(()) },
+                ResultadoProcesamiento { usuario: usuarios[3], resultado: Err(Error::INSCRIPCION_NO_PENDIENTE) },
+                ResultadoProcesamiento { usuario: usuarios[1], resultado: Ok(()) },
+            ]);
+            assert_eq!(contrato.obtener_siguiente_usuario_pendiente_en_una_eleccion(eleccion_id).unwrap().usuario_id, usuarios[0]);
+            assert_eq!(contrato.procesar_usuarios_en_una_eleccion(eleccion_id, false), Ok(usuarios[0]));
+            assert_eq!(contrato.procesar_usuarios_en_una_eleccion(eleccion_id, true), Ok(usuarios[4]));
+            en_fecha(FIN.checked_add(1).unwrap());
+            let padron:Vec<AccountId> = contrato.obtener_votantes_eleccion_por_id(eleccion_id, 0, 10).unwrap().iter()
+                .map(|votante| votante.usuario_id)
+                .collect();
+            assert_eq!(padron, vec![usuarios[3], usuarios[1], usuarios[4]]);
+        }
+
+        // ===================================================================================================
+        // =========================rechazos y apelaciones====================================================
+        // ===================================================================================================
+
+        /// Cada rechazo del sistema admite una apelación, que un registrador puede denegar o aceptar.
+        #[ink::test]
+        fn apelaciones_de_rechazos_del_sistema() {
+            let (mut contrato, usuarios) = contrato_con_pendientes(2);
+            let registrador = cuenta(2);
+            contrato.otorgar_rol(registrador, ROL::REGISTRADOR).unwrap();
+            en_fecha(1_000);
+            let motivo = MotivoRechazo { codigo: CODIGO_RECHAZO::DATOS_INCORRECTOS, detalle: String::from("DNI ilegible") };
+            contrato.rechazar_usuario_pendiente(usuarios[0], motivo.clone()).unwrap();
+            contrato.procesar_usuario_pendiente(usuarios[1], false).unwrap();
+
+            como(usuarios[1]);
+            assert_eq!(contrato.obtener_rechazo(usuarios[0], None), Err(Error::SIN_PERMISO));
+            como(usuarios[0]);
+            let rechazo = contrato.obtener_rechazo(usuarios[0], None).unwrap();
+            assert_eq!((rechazo.motivo, rechazo.rechazado_por, rechazo.fecha, rechazo.apelacion), (motivo, administrador(), 1_000, None));
+            contrato.apelar_rechazo(None, String::from("Adjunto una foto nueva")).unwrap();
+            assert_eq!(contrato.apelar_rechazo(None, String::from("Otra vez")), Err(Error::APELACION_YA_PRESENTADA));
+            como(usuarios[1]);
+            contrato.apelar_rechazo(None, String::from("Mis datos son correctos")).unwrap();
+            como(cuenta(99));
+            assert_eq!(contrato.apelar_rechazo(None, String::from("Texto")), Err(Error::NO_ESTA_RECHAZADO));
+
+            como(administrador());
+            assert_eq!(contrato.obtener_apelaciones_pendientes(0, 10), vec![
+                ApelacionPendiente { usuario: usuarios[0], eleccion_id: None },
+                ApelacionPendiente { usuario: usuarios[1], eleccion_id: None },
+            ]);
+            en_fecha(2_000);
+            contrato.resolver_apelacion(usuarios[1], None, RESOLUCION_APELACION::DENEGAR).unwrap();
+            let apelacion = contrato.obtener_rechazo(usuarios[1], None).unwrap().apelacion.unwrap();
+            assert_eq!((apelacion.resolucion, apelacion.resuelta_por, apelacion.fecha_resolucion), (Some(RESOLUCION_APELACION::DENEGAR), Some(administrador()), Some(2_000)));
+            como(usuarios[1]);
+            assert_eq!(contrato.apelar_rechazo(None, String::from("Texto")), Err(Error::APELACION_YA_PRESENTADA));
+
+            como(registrador);
+            contrato.resolver_apelacion(usuarios[0], None, RESOLUCION_APELACION::ACEPTAR).unwrap();
+            assert_eq!(contrato.resolver_apelacion(usuarios[0], None, RESOLUCION_APELACION::ACEPTAR), Err(Error::NO_HAY_APELACION_PENDIENTE));
+            assert_eq!(contrato.obtener_usuarios(0, 10), vec![usuarios[0]]);
+            assert_eq!(contrato.obtener_usuarios_rechazados(0, 10), vec![usuarios[1]]);
+            assert_eq!(contrato.obtener_cantidad_apelaciones_pendientes(), 0);
+            let historial = contrato.obtener_historial_rechazos(usuarios[0]).unwrap();
+            assert_eq!(historial.len(), 1);
+            assert_eq!(historial[0].apelacion.as_ref().and_then(|apelacion| apelacion.resuelta_por), Some(registrador));
+        }
+
+        /// Las apelaciones de una elección las resuelve su oficial electoral. Cada nuevo rechazo se suma al historial
+        /// y admite su propia apelación, y solo se puede reincorporar al usuario mientras la elección esté ABIERTA.
+        #[ink::test]
+        fn apelaciones_de_rechazos_en_una_eleccion() {
+            let (mut contrato, usuarios) = contrato_con_usuarios(1);
+            let eleccion_id = eleccion_abierta(&mut contrato, ConfiguracionEleccion::default(), &[], &[]);
+            let (registrador, oficial) = (cuenta(2), cuenta(3));
+            contrato.otorgar_rol(registrador, ROL::REGISTRADOR).unwrap();
+            contrato.otorgar_rol(oficial, ROL::OFICIAL_ELECTORAL(eleccion_id)).unwrap();
+            como(usuarios[0]);
+            contrato.inscribir_usuario_en_eleccion(eleccion_id, TIPO_DE_USUARIO::VOTANTE).unwrap();
+            como(oficial);
+            contrato.rechazar_inscripcion_en_eleccion(eleccion_id, usuarios[0], MotivoRechazo::default()).unwrap();
+
+            como(usuarios[0]);
+            assert_eq!(contrato.apelar_rechazo(None, String::from("Texto")), Err(Error::NO_ESTA_RECHAZADO));
+            contrato.apelar_rechazo(Some(eleccion_id), String::from("Soy alumno regular")).unwrap();
+            como(registrador);
+            assert_eq!(contrato.resolver_apelacion(usuarios[0], Some(eleccion_id), RESOLUCION_APELACION::ACEPTAR), Err(Error::SIN_PERMISO));
+            como(oficial);
+            contrato.resolver_apelacion(usuarios[0], Some(eleccion_id), RESOLUCION_APELACION::VOLVER_A_PENDIENTE).unwrap();
+            let solicitud = contrato.obtener_siguiente_usuario_pendiente_en_una_eleccion(eleccion_id).unwrap();
+            assert_eq!((solicitud.usuario_id, solicitud.tipo), (usuarios[0], TIPO_DE_USUARIO::VOTANTE));
+
+            contrato.procesar_usuarios_en_una_eleccion(eleccion_id, false).unwrap();
+            como(usuarios[0]);
+            contrato.apelar_rechazo(Some(eleccion_id), String::from("Sigo siendo alumno regular")).unwrap();
+            en_fecha(INICIO);
+            como(oficial);
+            assert_eq!(contrato.resolver_apelacion(usuarios[0], Some(eleccion_id), RESOLUCION_APELACION::ACEPTAR), Err(Error::VOTACION_INICIADA));
+            assert_eq!(contrato.obtener_cantidad_apelaciones_pendientes(), 1);
+            contrato.resolver_apelacion(usuarios[0], Some(eleccion_id), RESOLUCION_APELACION::DENEGAR).unwrap();
+
+            let historial = contrato.obtener_historial_rechazos(usuarios[0]);
+            assert_eq!(historial, Err(Error::SIN_PERMISO));
+            como(usuarios[0]);
+            let resoluciones:Vec<Option<RESOLUCION_APELACION>> = contrato.obtener_historial_rechazos(usuarios[0]).unwrap().iter()
+                .map(|rechazo| rechazo.apelacion.as_ref().and_then(|apelacion| apelacion.resolucion.clone()))
+                .collect();
+            assert_eq!(resoluciones, vec![Some(RESOLUCION_APELACION::VOLVER_A_PENDIENTE), Some(RESOLUCION_APELACION::DENEGAR)]);
+            assert_eq!(contrato.obtener_rechazo(usuarios[0], Some(eleccion_id)).unwrap().tipo, Some(TIPO_DE_USUARIO::VOTANTE));
+        }
+
+        // ===================================================================================================
+        // =========================estados de la elección====================================================
+        // ===================================================================================================
+
+        /// El estado que devuelven las consultas sigue a las fechas aunque nadie haya operado sobre la elección:
+        /// antes del inicio vale el que eligió el administrador, desde la fecha de inicio hasta la final inclusive
+        /// está INICIADA y después FINALIZADA.
+        #[ink::test]
+        fn el_estado_vigente_sigue_las_fechas() {
+            como(administrador());
+            let mut contrato = TrabajoFinal::new();
+            let fechas = || (String::from("01-01-2030 10:00"), String::from("01-01-2031 10:00"));
+            let (inicio, fin) = fechas();
+            let abierta = contrato.crear_eleccion(inicio, fin, ConfiguracionEleccion::default()).unwrap();
+            let (inicio, fin) = fechas();
+            let cerrada = contrato.crear_eleccion(inicio, fin, ConfiguracionEleccion::default()).unwrap();
+            assert_eq!(contrato.obtener_estado_eleccion(abierta), Ok(ESTADO_ELECCION::CERRADA));
+            contrato.abrir_eleccion(abierta).unwrap();
+
+            let estados = |contrato:&TrabajoFinal| (contrato.obtener_estado_eleccion(abierta).unwrap(), contrato.obtener_estado_eleccion(cerrada).unwrap());
+            en_fecha(INICIO.checked_sub(1).unwrap());
+            assert_eq!(estados(&contrato), (ESTADO_ELECCION::ABIERTA, ESTADO_ELECCION::CERRADA));
+            en_fecha(INICIO);
+            assert_eq!(estados(&contrato), (ESTADO_ELECCION::INICIADA, ESTADO_ELECCION::INICIADA));
+            en_fecha(FIN);
+            assert_eq!(estados(&contrato), (ESTADO_ELECCION::INICIADA, ESTADO_ELECCION::INICIADA));
+            en_fecha(FIN.checked_add(1).unwrap());
+            assert_eq!(estados(&contrato), (ESTADO_ELECCION::FINALIZADA, ESTADO_ELECCION::FINALIZADA));
+            assert_eq!(contrato.obtener_datos_eleccion_por_id(abierta).unwrap().estado, ESTADO_ELECCION::FINALIZADA);
+        }
+
+        /// La tabla publicada solo permite avanzar hacia la votación, y cualquier otro salto se rechaza.
+        #[ink::test]
+        fn las_transiciones_fuera_de_la_tabla_se_rechazan() {
+            como(administrador());
+            let mut contrato = TrabajoFinal::new();
+            let transiciones = contrato.obtener_transiciones_eleccion();
+            assert_eq!(transiciones, TRANSICIONES_ELECCION.to_vec());
+            assert!(transiciones.iter().all(|(desde, _)| *desde != ESTADO_ELECCION::FINALIZADA));
+
+            let estados = [ESTADO_ELECCION::CERRADA, ESTADO_ELECCION::ABIERTA, ESTADO_ELECCION::INICIADA, ESTADO_ELECCION::FINALIZADA];
+            for desde in estados.iter() {
+                for hacia in estados.iter().filter(|hacia| *hacia != desde) {
+                    let mut eleccion = contrato.nueva_eleccion(INICIO, FIN, ConfiguracionEleccion::default(), None).unwrap();
+                    eleccion.estado = desde.clone();
+                    let esperado = if transiciones.contains(&(desde.clone(), hacia.clone())) { Ok(()) } else { Err(Error::TRANSICION_INVALIDA) };
+                    assert_eq!(eleccion.cambiar_estado(hacia.clone()), esperado, "{:?} -> {:?}", desde, hacia);
+                }
+            }
+        }
+
+        /// Los mensajes manuales no permiten volver atrás ni adelantarse a las fechas.
+        #[ink::test]
+        fn los_mensajes_de_estado_respetan_las_fechas() {
+            let (mut contrato, _) = contrato_con_usuarios(0);
+            let eleccion_id = eleccion_abierta(&mut contrato, ConfiguracionEleccion::default(), &[], &[]);
+            en_fecha(INICIO.checked_sub(1).unwrap());
+            assert_eq!(contrato.iniciar_eleccion(eleccion_id), Err(Error::VOTACION_NO_INICIADA));
+            assert_eq!(contrato.finalizar_eleccion(eleccion_id), Err(Error::ELECCION_NO_FINALIZADA));
+
+            en_fecha(INICIO);
+            assert_eq!(contrato.cerrar_eleccion(eleccion_id), Err(Error::VOTACION_INICIADA));
+            assert_eq!(contrato.abrir_eleccion(eleccion_id), Err(Error::VOTACION_INICIADA));
+            assert_eq!(contrato.finalizar_eleccion(eleccion_id), Err(Error::ELECCION_NO_FINALIZADA));
+
+            en_fecha(FIN.checked_add(1).unwrap());
+            assert_eq!(contrato.abrir_eleccion(eleccion_id), Err(Error::ELECCION_FINALIZADA));
+            assert_eq!(contrato.cerrar_eleccion(eleccion_id), Err(Error::ELECCION_FINALIZADA));
+            assert_eq!(contrato.iniciar_eleccion(eleccion_id), Err(Error::ELECCION_FINALIZADA));
+        }
+
+        /// Una vez pasada la fecha final cualquier cuenta puede disparar el escrutinio, aunque no tenga rol alguno.
+        #[ink::test]
+        fn cualquier_cuenta_puede_finalizar_la_eleccion() {
+            let (mut contrato, usuarios) = contrato_con_usuarios(3);
+            let eleccion_id = eleccion_abierta(&mut contrato, ConfiguracionEleccion::default(), &usuarios[..1], &usuarios[1..]);
+            en_fecha(INICIO);
+            for votante in usuarios[1..].iter() {
+                como(*votante);
+                contrato.votar_a_candidato(eleccion_id, 1).unwrap();
+            }
+
+            como(cuenta(99));
+            assert_eq!(contrato.finalizar_eleccion(eleccion_id), Err(Error::ELECCION_NO_FINALIZADA));
+            en_fecha(FIN.checked_add(1).unwrap());
+            assert_eq!(contrato.finalizar_eleccion(eleccion_id), Ok(()));
+            assert_eq!(contrato.finalizar_eleccion(eleccion_id), Err(Error::ELECCION_YA_EN_ESTADO));
+            assert_eq!(ganador(&contrato, eleccion_id), Some(1));
+        }
+
+        // ===================================================================================================
+        // =========================traspaso de administración================================================
+        // ===================================================================================================
+
+        fn crear_eleccion_de_prueba(contrato:&mut TrabajoFinal) -> Result<u64> {
+            contrato.crear_eleccion(String::from("01-01-2030 10:00"), String::from("01-01-2031 10:00"), ConfiguracionEleccion::default())
+        }
+
+        /// Sin espera, el traspaso se completa cuando lo acepta la cuenta propuesta y el anterior pierde el control.
+        #[ink::test]
+        fn traspaso_de_administrador_sin_espera() {
+            let (mut contrato, _) = contrato_con_usuarios(0);
+            let sucesor = cuenta(2);
+            en_fecha(1_000);
+            assert_eq!(contrato.proponer_administrador(administrador(), 100, 0), Err(Error::TRASPASO_INVALIDO));
+            assert_eq!(contrato.proponer_administrador(sucesor, 0, 0), Err(Error::TRASPASO_INVALIDO));
+            como(sucesor);
+            assert_eq!(contrato.proponer_administrador(sucesor, 100, 0), Err(Error::NO_ES_ADMINISTRADOR));
+
+            como(administrador());
+            contrato.proponer_administrador(sucesor, 100, 0).unwrap();
+            assert_eq!(contrato.obtener_traspaso_administrador().map(|traspaso| (traspaso.propuesto, traspaso.vencimiento)), Some((sucesor, 1_100)));
+            assert_eq!(contrato.proponer_administrador(cuenta(3), 100, 0), Err(Error::TRASPASO_EN_CURSO));
+            como(cuenta(3));
+            assert_eq!(contrato.aceptar_administrador(), Err(Error::NO_ES_ADMINISTRADOR_PROPUESTO));
+
+            en_fecha(1_100);
+            como(sucesor);
+            contrato.aceptar_administrador().unwrap();
+            assert_eq!(contrato.obtener_administrador(), Some(sucesor));
+            assert_eq!(contrato.obtener_traspaso_administrador(), None);
+            assert!(crear_eleccion_de_prueba(&mut contrato).is_ok());
+            como(administrador());
+            assert_eq!(crear_eleccion_de_prueba(&mut contrato), Err(Error::NO_ES_ADMINISTRADOR));
+        }
+
+        /// Con espera, el administrador anterior conserva el control entre la aceptación y el fin de la espera.
+        #[ink::test]
+        fn traspaso_de_administrador_con_espera() {
+            let (mut contrato, _) = contrato_con_usuarios(0);
+            let sucesor = cuenta(2);
+            en_fecha(1_000);
+            contrato.proponer_administrador(sucesor, 100, 500).unwrap();
+            como(sucesor);
+            assert_eq!(contrato.completar_traspaso_administrador(), Err(Error::TRASPASO_NO_ACEPTADO));
+            en_fecha(1_050);
+            contrato.aceptar_administrador().unwrap();
+            assert_eq!(contrato.aceptar_administrador(), Err(Error::TRASPASO_YA_ACEPTADO));
+            assert_eq!(contrato.obtener_traspaso_administrador().and_then(|traspaso| traspaso.efectivo_desde), Some(1_550));
+
+            en_fecha(1_549);
+            assert_eq!(contrato.completar_traspaso_administrador(), Err(Error::ESPERA_EN_CURSO));
+            assert_eq!(contrato.obtener_administrador(), Some(administrador()));
+            assert_eq!(crear_eleccion_de_prueba(&mut contrato), Err(Error::NO_ES_ADMINISTRADOR));
+
+            en_fecha(1_550);
+            contrato.completar_traspaso_administrador().unwrap();
+            assert_eq!(contrato.obtener_administrador(), Some(sucesor));
+        }
+
+        /// Una propuesta vencida no se puede aceptar y bloquea nuevas propuestas hasta que se cancele.
+        /// También se puede cancelar una propuesta ya aceptada mientras dure la espera.
+        #[ink::test]
+        fn traspaso_de_administrador_vencido_y_cancelado() {
+            let (mut contrato, _) = contrato_con_usuarios(0);
+            let sucesor = cuenta(2);
+            en_fecha(1_000);
+            contrato.proponer_administrador(cuenta(3), 100, 0).unwrap();
+            en_fecha(1_101);
+            como(cuenta(3));
+            assert_eq!(contrato.aceptar_administrador(), Err(Error::TRASPASO_VENCIDO));
+            assert_eq!(contrato.cancelar_traspaso_administrador(), Err(Error::NO_ES_ADMINISTRADOR));
+
+            como(administrador());
+            assert_eq!(contrato.proponer_administrador(sucesor, 100, 500), Err(Error::TRASPASO_EN_CURSO));
+            contrato.cancelar_traspaso_administrador().unwrap();
+            assert_eq!(contrato.cancelar_traspaso_administrador(), Err(Error::NO_HAY_TRASPASO));
+            contrato.proponer_administrador(sucesor, 100, 500).unwrap();
+            como(sucesor);
+            contrato.aceptar_administrador().unwrap();
+
+            como(administrador());
+            contrato.cancelar_traspaso_administrador().unwrap();
+            en_fecha(2_000);
+            como(sucesor);
+            assert_eq!(contrato.completar_traspaso_administrador(), Err(Error::NO_HAY_TRASPASO));
+            assert_eq!(contrato.obtener_administrador(), Some(administrador()));
         }
-        #[ink(message)] //FUNCIONA
-        pub fn obtener_usuarios(&self) -> Vec<AccountId>
-        {
-            self.usuarios.iter().map(|usuario| usuario.id).collect()
+
+        /// La renuncia exige confirmar la cuenta del administrador y no convive con un traspaso en curso.
+        /// Después nadie puede usar los mensajes exclusivos del administrador, pero los roles otorgados siguen vigentes.
+        #[ink::test]
+        fn renuncia_a_la_administracion() {
+            let (mut contrato, usuarios) = contrato_con_pendientes(1);
+            let registrador = cuenta(2);
+            contrato.otorgar_rol(registrador, ROL::REGISTRADOR).unwrap();
+            assert_eq!(contrato.renunciar_administracion(registrador), Err(Error::NO_ES_ADMINISTRADOR));
+            contrato.proponer_administrador(registrador, 100, 0).unwrap();
+            assert_eq!(contrato.renunciar_administracion(administrador()), Err(Error::TRASPASO_EN_CURSO));
+            contrato.cancelar_traspaso_administrador().unwrap();
+
+            contrato.renunciar_administracion(administrador()).unwrap();
+            assert_eq!(contrato.obtener_administrador(), None);
+            assert_eq!(crear_eleccion_de_prueba(&mut contrato), Err(Error::NO_ES_ADMINISTRADOR));
+            assert_eq!(contrato.proponer_administrador(registrador, 100, 0), Err(Error::NO_ES_ADMINISTRADOR));
+            como(registrador);
+            assert_eq!(contrato.procesar_siguiente_usuario_pendiente(true), Ok(usuarios[0]));
         }
-        #[ink(message)] //FUNCIONA
-        pub fn obtener_usuarios_rechazados(&self) -> Vec<AccountId>
-        {
-            self.usuarios_rechazados.iter().map(|usuario| usuario.id).collect()
+
+        // ===================================================================================================
+        // =========================bajas de candidatos y votantes============================================
+        // ===================================================================================================
+
+        /// Un candidato retirado desaparece de los resultados y no recibe votos. Si vuelve a inscribirse recibe un id
+        /// nuevo, y una vez empezada la votación ya no puede retirarse.
+        #[ink::test]
+        fn retirar_candidatura_antes_de_la_votacion() {
+            let (mut contrato, usuarios) = contrato_con_usuarios(4);
+            let eleccion_id = eleccion_abierta(&mut contrato, ConfiguracionEleccion::default(), &usuarios[..3], &usuarios[3..]);
+            como(usuarios[3]);
+            assert_eq!(contrato.retirar_candidatura(eleccion_id), Err(Error::NO_ES_CANDIDATO));
+            como(usuarios[1]);
+            contrato.retirar_candidatura(eleccion_id).unwrap();
+            assert_eq!(contrato.obtener_informacion_candidato(eleccion_id, 2), Err(Error::CANDIDATO_NO_EXISTE));
+            assert_eq!(contrato.retirar_candidatura(eleccion_id), Err(Error::NO_ES_CANDIDATO));
+            contrato.inscribir_usuario_en_eleccion(eleccion_id, TIPO_DE_USUARIO::CANDIDATO).unwrap();
+            como(administrador());
+            contrato.procesar_usuarios_en_una_eleccion(eleccion_id, true).unwrap();
+            assert_eq!(contrato.obtener_informacion_candidato(eleccion_id, 4).unwrap().usuario_id, usuarios[1]);
+
+            en_fecha(INICIO);
+            como(usuarios[2]);
+            assert_eq!(contrato.retirar_candidatura(eleccion_id), Err(Error::VOTACION_INICIADA));
+            como(usuarios[3]);
+            assert_eq!(contrato.votar_a_candidato(eleccion_id, 2), Err(Error::CANDIDATO_NO_EXISTE));
+            contrato.votar_a_candidato(eleccion_id, 4).unwrap();
+            finalizar(&mut contrato, eleccion_id);
+            let votos:Vec<(u64, u64)> = contrato.obtener_candidatos_eleccion_por_id(eleccion_id).unwrap().iter()
+                .map(|candidato| (candidato.id, candidato.votos_totales))
+                .collect();
+            assert_eq!(votos, vec![(1, 0), (3, 0), (4, 1)]);
+            assert_eq!(ganador(&contrato, eleccion_id), Some(4));
         }
 
-        #[ink(message)] //FUNCIONA
-        pub fn obtener_datos_usuario_pendiente_por_id(&mut self, id_usuario: AccountId) -> Result<String, String> 
-        {
-            let sig_usuario = self.obtener_usuario_pendiente_por_id(id_usuario);
-            match sig_usuario {
-                Some(usuario) => {
-                    let mut str = String::from("Nombre: ") + usuario.nombre.as_str();
-                    str.push_str((String::from("\nApellido: ") + usuario.apellido.as_str()).as_str());
-                    str.push_str((String::from("\nDNI: ") + usuario.apellido.as_str()).as_str());
-                    Ok(str)
-                },
-                None => Err(String::from("No hay usuarios con tal id.")),
+        /// Un candidato que integra una lista solo puede retirarse después de que se elimine la lista.
+        #[ink::test]
+        fn retirar_candidatura_de_una_lista() {
+            let (mut contrato, usuarios) = contrato_con_usuarios(2);
+            for (usuario, genero) in usuarios.iter().zip([GENERO::FEMENINO, GENERO::MASCULINO]) {
+                como(*usuario);
+                contrato.declarar_genero(genero).unwrap();
             }
+            let configuracion = ConfiguracionEleccion { sistema_escrutinio: SISTEMA_ESCRUTINIO::DHONDT { bancas: 1, umbral: 0 }, ..Default::default() };
+            let eleccion_id = eleccion_abierta(&mut contrato, configuracion, &usuarios, &[]);
+            let lista_id = contrato.crear_lista(eleccion_id, 1, String::from("Lista 1"), vec![1, 2]).unwrap();
+            como(usuarios[0]);
+            assert_eq!(contrato.retirar_candidatura(eleccion_id), Err(Error::CANDIDATO_EN_LISTA));
+            como(administrador());
+            contrato.eliminar_lista(eleccion_id, lista_id).unwrap();
+            como(usuarios[0]);
+            contrato.retirar_candidatura(eleccion_id).unwrap();
         }
-        #[ink(message)] //FUNCIONA
-        pub fn obtener_datos_usuario_por_id(&mut self, id_usuario: AccountId) -> Result<String, String> 
-        {
-            let sig_usuario = self.obtener_usuario_por_id(id_usuario);
-            match sig_usuario {
-                Some(usuario) => {
-                    let mut str = String::from("Nombre: ") + usuario.nombre.as_str();
-                    str.push_str((String::from("\nApellido: ") + usuario.apellido.as_str()).as_str());
-                    str.push_str((String::from("\nDNI: ") + usuario.apellido.as_str()).as_str());
-                    Ok(str)
-                },
-                None => Err(String::from("No hay usuarios con tal id.")),
+
+        /// Un votante dado de baja sale del padrón y no puede votar, salvo que vuelva a inscribirse antes de la votación.
+        #[ink::test]
+        fn darse_de_baja_como_votante_antes_de_la_votacion() {
+            let (mut contrato, usuarios) = contrato_con_usuarios(4);
+            let eleccion_id = eleccion_abierta(&mut contrato, ConfiguracionEleccion::default(), &usuarios[..1], &usuarios[1..]);
+            como(usuarios[0]);
+            assert_eq!(contrato.darse_de_baja_como_votante(eleccion_id), Err(Error::NO_ES_VOTANTE));
+            como(usuarios[1]);
+            contrato.darse_de_baja_como_votante(eleccion_id).unwrap();
+            assert_eq!(contrato.darse_de_baja_como_votante(eleccion_id), Err(Error::NO_ES_VOTANTE));
+            como(usuarios[2]);
+            contrato.darse_de_baja_como_votante(eleccion_id).unwrap();
+            contrato.inscribir_usuario_en_eleccion(eleccion_id, TIPO_DE_USUARIO::VOTANTE).unwrap();
+            como(administrador());
+            contrato.procesar_usuarios_en_una_eleccion(eleccion_id, true).unwrap();
+            assert_eq!(contrato.obtener_datos_eleccion_por_id(eleccion_id).unwrap().cantidad_votantes, 2);
+
+            en_fecha(INICIO);
+            como(usuarios[3]);
+            assert_eq!(contrato.darse_de_baja_como_votante(eleccion_id), Err(Error::VOTACION_INICIADA));
+            como(usuarios[1]);
+            assert_eq!(contrato.votar_a_candidato(eleccion_id, 1), Err(Error::NO_ES_VOTANTE));
+            como(usuarios[2]);
+            contrato.votar_a_candidato(eleccion_id, 1).unwrap();
+
+            en_fecha(FIN.checked_add(1).unwrap());
+            let mut padron:Vec<(AccountId, bool)> = contrato.obtener_votantes_eleccion_por_id(eleccion_id, 0, 10).unwrap().iter()
+                .map(|votante| (votante.usuario_id, votante.voto_emitido))
+                .collect();
+            padron.sort();
+            assert_eq!(padron, vec![(usuarios[2], true), (usuarios[3], false)]);
+        }
+
+        // ===================================================================================================
+        // =========================reportes==================================================================
+        // ===================================================================================================
+
+        /// El resumen que leen los reportes cuenta los votos emitidos, incluidos los blancos, sin recorrer
+        /// el padrón, y el padrón se entrega por páginas.
+        #[ink::test]
+        fn el_resumen_cuenta_los_votos_emitidos_y_el_padron_se_pagina() {
+            let (mut contrato, usuarios) = contrato_con_usuarios(5);
+            let eleccion_id = eleccion_abierta(&mut contrato, ConfiguracionEleccion::default(), &usuarios[..2], &usuarios[2..]);
+            let reportes = cuenta(99);
+            contrato.establecer_contrato_reportes(Some(reportes)).unwrap();
+            en_fecha(INICIO);
+            como(usuarios[2]);
+            contrato.votar_a_candidato(eleccion_id, 1).unwrap();
+            como(usuarios[4]);
+            contrato.votar_en_blanco(eleccion_id).unwrap();
+
+            como(reportes);
+            let resumen = contrato.obtener_resumen_eleccion(eleccion_id).unwrap();
+            assert_eq!((resumen.cantidad_votantes, resumen.votos_emitidos), (3, 2));
+            let padron:Vec<(AccountId, bool)> = contrato.obtener_padron_eleccion(eleccion_id, 1, 5).unwrap().iter()
+                .map(|votante| (votante.usuario_id, votante.voto_emitido))
+                .collect();
+            assert_eq!(padron, vec![(usuarios[3], false), (usuarios[4], true)]);
+            assert_eq!(contrato.obtener_padron_eleccion(eleccion_id, 3, 5), Ok(Vec::new()));
+            como(usuarios[2]);
+            assert_eq!(contrato.obtener_padron_eleccion(eleccion_id, 0, 5), Err(Error::NO_ES_CONTRATO_REPORTES));
+        }
+
+        /// Los datos, los resultados y el padrón de una elección se pueden consultar sin ningún rol,
+        /// los dos últimos recién cuando terminó la votación.
+        #[ink::test]
+        fn las_consultas_de_la_eleccion_son_publicas() {
+            let (mut contrato, usuarios) = contrato_con_usuarios(3);
+            let eleccion_id = eleccion_abierta(&mut contrato, ConfiguracionEleccion::default(), &usuarios[..1], &usuarios[1..]);
+            en_fecha(INICIO);
+            como(usuarios[1]);
+            contrato.votar_a_candidato(eleccion_id, 1).unwrap();
+
+            como(cuenta(99));
+            assert_eq!(contrato.obtener_datos_eleccion_por_id(eleccion_id).unwrap().votos_emitidos, 1);
+            assert_eq!(contrato.obtener_candidatos_eleccion_por_id(eleccion_id).map(|_| ()), Err(Error::ELECCION_NO_FINALIZADA));
+            assert_eq!(contrato.obtener_votantes_eleccion_por_id(eleccion_id, 0, 5).map(|_| ()), Err(Error::ELECCION_NO_FINALIZADA));
+
+            en_fecha(FIN.checked_add(1).unwrap());
+            let votos:Vec<(u64, u64)> = contrato.obtener_candidatos_eleccion_por_id(eleccion_id).unwrap().iter()
+                .map(|candidato| (candidato.id, candidato.votos_totales))
+                .collect();
+            assert_eq!(votos, vec![(1, 1)]);
+            let padron:Vec<(AccountId, bool)> = contrato.obtener_votantes_eleccion_por_id(eleccion_id, 0, 5).unwrap().iter()
+                .map(|votante| (votante.usuario_id, votante.voto_emitido))
+                .collect();
+            assert_eq!(padron, vec![(usuarios[1], true), (usuarios[2], false)]);
+        }
+
+        // ===================================================================================================
+        // =========================escrutinio preferencial===================================================
+        // ===================================================================================================
+
+        /// Ejemplo clásico de la capital de Tennessee: 1 Memphis, 2 Nashville, 3 Chattanooga, 4 Knoxville.
+        /// Se elimina a Chattanooga, luego a Nashville, y Knoxville gana con 58 de 100.
+        #[ink::test]
+        fn preferencial_capital_de_tennessee() {
+            let (contrato, eleccion_id) = eleccion_con_boletas(SISTEMA_ESCRUTINIO::PREFERENCIAL, 4, &[
+                (42, &[1, 2, 3, 4]),
+                (26, &[2, 3, 4, 1]),
+                (15, &[3, 4, 2, 1]),
+                (17, &[4, 3, 2, 1]),
+            ]);
+            let rondas = contrato.obtener_rondas_escrutinio(eleccion_id).unwrap();
+            assert_eq!(rondas.len(), 3);
+            assert_eq!(votos_de_ronda(&rondas[0]), vec![(1, 42), (2, 26), (3, 15), (4, 17)]);
+            assert_eq!((rondas[0].eliminado, rondas[0].ganador), (Some(3), None));
+            assert_eq!(votos_de_ronda(&rondas[1]), vec![(1, 42), (2, 26), (4, 32)]);
+            assert_eq!((rondas[1].eliminado, rondas[1].ganador), (Some(2), None));
+            assert_eq!(votos_de_ronda(&rondas[2]), vec![(1, 42), (4, 58)]);
+            assert_eq!((rondas[2].eliminado, rondas[2].ganador), (None, Some(4)));
+            assert_eq!(ganador(&contrato, eleccion_id), Some(4));
+        }
+
+        #[ink::test]
+        fn preferencial_mayoria_en_primera_ronda() {
+            let (contrato, eleccion_id) = eleccion_con_boletas(SISTEMA_ESCRUTINIO::PREFERENCIAL, 3, &[(5, &[2]), (3, &[1, 2]), (1, &[3])]);
+            let rondas = contrato.obtener_rondas_escrutinio(eleccion_id).unwrap();
+            assert_eq!(rondas.len(), 1);
+            assert_eq!(rondas[0].ganador, Some(2));
+            assert_eq!(ganador(&contrato, eleccion_id), Some(2));
+        }
+
+        #[ink::test]
+        fn preferencial_empate_final_sin_ganador() {
+            let (contrato, eleccion_id) = eleccion_con_boletas(SISTEMA_ESCRUTINIO::PREFERENCIAL, 2, &[(5, &[1, 2]), (5, &[2, 1])]);
+            let rondas = contrato.obtener_rondas_escrutinio(eleccion_id).unwrap();
+            assert_eq!(rondas.len(), 1);
+            assert_eq!((rondas[0].eliminado, rondas[0].ganador), (None, None));
+            assert_eq!(ganador(&contrato, eleccion_id), None);
+        }
+
+        /// El empate en el último lugar elimina al de mayor id aunque eso decida el resultado.
+        #[ink::test]
+        fn preferencial_empate_en_el_ultimo_lugar_elimina_al_mayor_id() {
+            let (contrato, eleccion_id) = eleccion_con_boletas(SISTEMA_ESCRUTINIO::PREFERENCIAL, 3, &[(3, &[1]), (2, &[2, 1]), (2, &[3, 2])]);
+            let rondas = contrato.obtener_rondas_escrutinio(eleccion_id).unwrap();
+            assert_eq!(rondas.len(), 2);
+            assert_eq!(rondas[0].eliminado, Some(3));
+            assert_eq!(votos_de_ronda(&rondas[1]), vec![(1, 3), (2, 4)]);
+            assert_eq!(rondas[1].ganador, Some(2));
+        }
+
+        /// Las boletas que se quedan sin candidatos en carrera dejan de contar para la mayoría.
+        #[ink::test]
+        fn preferencial_boletas_agotadas() {
+            let (contrato, eleccion_id) = eleccion_con_boletas(SISTEMA_ESCRUTINIO::PREFERENCIAL, 3, &[(4, &[1]), (3, &[2]), (2, &[3])]);
+            let rondas = contrato.obtener_rondas_escrutinio(eleccion_id).unwrap();
+            assert_eq!(rondas.len(), 2);
+            assert_eq!(rondas[1].boletas_agotadas, 2);
+            assert_eq!(rondas[1].ganador, Some(1));
+        }
+
+        /// Con lotes de 4 boletas, las dos rondas de 9 boletas se cuentan en cinco llamadas y los
+        /// resultados solo se publican al terminar.
+        #[ink::test]
+        fn preferencial_se_escruta_por_partes() {
+            let (mut contrato, eleccion_id) = eleccion_votada(SISTEMA_ESCRUTINIO::PREFERENCIAL, 3, &[(4, &[1]), (3, &[2]), (2, &[3])]);
+            como(administrador());
+            assert_eq!(contrato.escrutar_eleccion(eleccion_id, 4), Err(Error::ELECCION_NO_FINALIZADA));
+
+            en_fecha(FIN.checked_add(1).unwrap());
+            for _ in 0..4 {
+                assert_eq!(contrato.escrutar_eleccion(eleccion_id, 4), Ok(false));
+                assert_eq!(contrato.obtener_rondas_escrutinio(eleccion_id), Err(Error::ELECCION_NO_FINALIZADA));
             }
+            assert_eq!(contrato.escrutar_eleccion(eleccion_id, 4), Ok(true));
+            assert_eq!(contrato.escrutar_eleccion(eleccion_id, 4), Err(Error::ELECCION_YA_EN_ESTADO));
+
+            let rondas = contrato.obtener_rondas_escrutinio(eleccion_id).unwrap();
+            assert_eq!(rondas.len(), 2);
+            assert_eq!(votos_de_ronda(&rondas[0]), vec![(1, 4), (2, 3), (3, 2)]);
+            assert_eq!(rondas[0].eliminado, Some(3));
+            assert_eq!((rondas[1].boletas_agotadas, rondas[1].ganador), (2, Some(1)));
+            assert_eq!(ganador(&contrato, eleccion_id), Some(1));
         }
-        #[ink(message)] //FUNCIONA
-        pub fn obtener_datos_usuario_rechazado_por_id(&mut self, id_usuario: AccountId) -> Result<String, String> 
-        {
-            let sig_usuario = self.obtener_usuario_rechazado_por_id(id_usuario);
-            match sig_usuario {
-                Some(usuario) => {
-                    let mut str = String::from("Nombre: ") + usuario.nombre.as_str();
-                    str.push_str((String::from("\nApellido: ") + usuario.apellido.as_str()).as_str());
-                    str.push_str((String::from("\nDNI: ") + usuario.apellido.as_str()).as_str());
-                    Ok(str)
-                },
-                None => Err(String::from("No hay usuarios con tal id.")),
+
+        // ===================================================================================================
+        // =========================balotaje==================================================================
+        // ===================================================================================================
+
+        #[test]
+        fn primera_vuelta_con_mas_del_45_por_ciento() {
+            assert_eq!(TrabajoFinal::gana_en_primera_vuelta(46, 44, 100), Ok(true));
+            assert_eq!(TrabajoFinal::gana_en_primera_vuelta(451, 449, 1000), Ok(true));
+            assert_eq!(TrabajoFinal::gana_en_primera_vuelta(45, 44, 100), Ok(false));
+        }
+
+        #[test]
+        fn primera_vuelta_con_40_por_ciento_y_mas_de_10_puntos() {
+            assert_eq!(TrabajoFinal::gana_en_primera_vuelta(40, 29, 100), Ok(true));
+            assert_eq!(TrabajoFinal::gana_en_primera_vuelta(40, 30, 100), Ok(false));
+            assert_eq!(TrabajoFinal::gana_en_primera_vuelta(39, 10, 100), Ok(false));
+        }
+
+        /// Con los blancos en la base, los mismos votos a candidatos pueden dejar de alcanzar.
+        #[test]
+        fn primera_vuelta_depende_de_la_base() {
+            assert_eq!(TrabajoFinal::gana_en_primera_vuelta(46, 30, 76), Ok(true));
+            assert_eq!(TrabajoFinal::gana_en_primera_vuelta(46, 30, 116), Ok(false));
+        }
+
+        /// Fechas de una segunda vuelta de prueba, 01-01-2032 10:00 y 01-01-2033 10:00, en milisegundos.
+        const INICIO_SEGUNDA_VUELTA:u64 = 1_956_564_000_000;
+        const FIN_SEGUNDA_VUELTA:u64 = 1_988_186_400_000;
+
+        /// Elección por mayoría simple con balotaje y tres candidatos en la que los votantes votan en orden
+        /// a los candidatos indicados. Se devuelve ya finalizada junto con los votantes.
+        fn eleccion_con_balotaje(votos:&[u64]) -> (TrabajoFinal, u64, Vec<AccountId>) {
+            eleccion_con_balotaje_y_blancos(false, votos, 0, 0)
+        }
+        /// Como `eleccion_con_balotaje`, pero después votan `blancos` votantes en blanco y `nulos` votantes nulo.
+        fn eleccion_con_balotaje_y_blancos(blancos_en_base:bool, votos:&[u64], blancos:usize, nulos:usize) -> (TrabajoFinal, u64, Vec<AccountId>) {
+            let votantes = votos.len().checked_add(blancos).and_then(|votantes| votantes.checked_add(nulos)).unwrap();
+            let (mut contrato, usuarios) = contrato_con_usuarios(u8::try_from(votantes.checked_add(3).unwrap()).unwrap());
+            let configuracion = ConfiguracionEleccion { balotaje: true, blancos_en_base, ..Default::default() };
+            let eleccion_id = eleccion_abierta(&mut contrato, configuracion, &usuarios[..3], &usuarios[3..]);
+            en_fecha(INICIO);
+            let mut votantes = usuarios[3..].iter();
+            for (candidato_id, votante) in votos.iter().zip(votantes.by_ref()) {
+                como(*votante);
+                contrato.votar_a_candidato(eleccion_id, *candidato_id).unwrap();
+            }
+            for votante in votantes.by_ref().take(blancos) {
+                como(*votante);
+                contrato.votar_en_blanco(eleccion_id).unwrap();
             }
+            for votante in votantes {
+                como(*votante);
+                contrato.votar_nulo(eleccion_id).unwrap();
+            }
+            finalizar(&mut contrato, eleccion_id);
+            (contrato, eleccion_id, usuarios)
         }
 
+        /// Con 5 y 4 votos, 3 blancos y 2 nulos, sin los blancos en la base el primero tiene 5 de 9 y gana.
+        #[ink::test]
+        fn sin_los_blancos_en_la_base_gana_en_primera_vuelta() {
+            let (contrato, eleccion_id, _) = eleccion_con_balotaje_y_blancos(false, &[1, 1, 1, 1, 1, 2, 2, 2, 2], 3, 2);
+            let resumen = contrato.obtener_datos_eleccion_por_id(eleccion_id).unwrap();
+            assert_eq!((resumen.ganador, resumen.segunda_vuelta, resumen.votos_emitidos), (Some(1), None, 14));
+            assert_eq!(contrato.obtener_votos_blancos_y_nulos(eleccion_id), Ok(VotosBlancosYNulos { votos_en_blanco: 3, votos_nulos: 2 }));
+        }
 
-        /// Utilizado por un administrador.
-        /// Activa el registro de usuarios si no está activo el registro.
-        #[ink(message)] //FUNCIONA
-        pub fn activar_registro(&mut self) -> Result<String, String> 
-        {
-            if !self.es_administrador() { return Err(ERRORES::NO_ES_ADMINISTRADOR.to_string()); }
-            if self.registro_activado { return Err(String::from("El registro ya está activado.")); }
-            self.registro_activado = true;
-            return Ok(String::from("Se activó el registro para los usuarios."));
+        /// Los mismos votos con los blancos en la base: el primero tiene 5 de 12 (41,7%) y le saca 8,3 puntos
+        /// al segundo, así que hay segunda vuelta.
+        #[ink::test]
+        fn con_los_blancos_en_la_base_hay_segunda_vuelta() {
+            let (contrato, eleccion_id, _) = eleccion_con_balotaje_y_blancos(true, &[1, 1, 1, 1, 1, 2, 2, 2, 2], 3, 2);
+            let resumen = contrato.obtener_datos_eleccion_por_id(eleccion_id).unwrap();
+            assert_eq!((resumen.ganador, resumen.segunda_vuelta), (None, Some(2)));
         }
-        /// Utilizado por un administrador.
-        /// Desactiva el registro de usuarios si no está activo el registro.
-        #[ink(message)] //FUNCIONA
-        pub fn desactivar_registro(&mut self) -> Result<String, String> 
-        {
-            if !self.es_administrador() { return Err(ERRORES::NO_ES_ADMINISTRADOR.to_string()); }
-            if !self.registro_activado { return Err(String::from("El registro ya está desactivado.")); }
-            self.registro_activado = false;
-            return Ok(String::from("Se desactivó el registro para los usuarios."));
+
+        /// Los nulos nunca integran la base: con 5 y 4 votos y 5 nulos el primero gana aunque los blancos estén en la base.
+        #[ink::test]
+        fn los_nulos_no_integran_la_base() {
+            let (contrato, eleccion_id, _) = eleccion_con_balotaje_y_blancos(true, &[1, 1, 1, 1, 1, 2, 2, 2, 2], 0, 5);
+            let resumen = contrato.obtener_datos_eleccion_por_id(eleccion_id).unwrap();
+            assert_eq!((resumen.ganador, resumen.segunda_vuelta), (Some(1), None));
+            assert_eq!(contrato.obtener_votos_blancos_y_nulos(eleccion_id), Ok(VotosBlancosYNulos { votos_en_blanco: 0, votos_nulos: 5 }));
         }
 
+        #[ink::test]
+        fn balotaje_gana_en_primera_vuelta() {
+            let (contrato, eleccion_id, _) = eleccion_con_balotaje(&[1, 1, 1, 1, 1, 2, 2, 2, 2, 3]);
+            let resumen = contrato.obtener_datos_eleccion_por_id(eleccion_id).unwrap();
+            assert_eq!((resumen.ganador, resumen.segunda_vuelta), (Some(1), None));
+        }
 
-    // ===================================================================================================
-    // ===================================================================================================
-    // ===================================================================================================
+        /// Con 4, 3 y 3 votos nadie alcanza lo necesario: la segunda vuelta se disputa entre el primero y el
+        /// segundo, que por el empate es el de menor id, con el padrón de la primera y sin fechas hasta que se programa.
+        #[ink::test]
+        fn balotaje_con_segunda_vuelta() {
+            let (mut contrato, eleccion_id, usuarios) = eleccion_con_balotaje(&[1, 1, 1, 1, 2, 2, 2, 3, 3, 3]);
+            let resumen = contrato.obtener_datos_eleccion_por_id(eleccion_id).unwrap();
+            assert_eq!((resumen.ganador, resumen.segunda_vuelta), (None, Some(2)));
 
+            let segunda_vuelta = contrato.obtener_datos_eleccion_por_id(2).unwrap();
+            assert_eq!((segunda_vuelta.eleccion_anterior, segunda_vuelta.cantidad_votantes), (Some(eleccion_id), 0));
+            let finalistas:Vec<AccountId> = contrato.obtener_candidatos_eleccion_por_id(2).unwrap().iter()
+                .map(|candidato| candidato.usuario_id)
+                .collect();
+            assert_eq!(finalistas, vec![usuarios[0], usuarios[1]]);
+            como(usuarios[3]);
+            assert_eq!(contrato.votar_a_candidato(2, 1), Err(Error::FECHAS_NO_DEFINIDAS));
 
-    // ===================================================================================================
-    // =========================creacion y administracion de estados de elecciones========================
-    // ===================================================================================================
+            // El padrón se copia por partes y la segunda vuelta no se puede programar hasta completarlo.
+            como(administrador());
+            assert_eq!(contrato.copiar_padron_segunda_vuelta(2, 4), Ok(6));
+            let programar = |contrato:&mut TrabajoFinal| {
+                contrato.programar_segunda_vuelta(2, String::from("01-01-2032 10:00"), String::from("01-01-2033 10:00"))
+            };
+            assert_eq!(programar(&mut contrato), Err(Error::PADRON_INCOMPLETO));
+            assert_eq!(contrato.copiar_padron_segunda_vuelta(2, 100), Ok(0));
+            let ultimos:Vec<AccountId> = contrato.obtener_votantes_eleccion_por_id(2, 8, 100).unwrap().iter()
+                .map(|votante| votante.usuario_id)
+                .collect();
+            assert_eq!(ultimos, usuarios[11..].to_vec());
+            programar(&mut contrato).unwrap();
+            assert_eq!(contrato.copiar_padron_segunda_vuelta(2, 100), Err(Error::SEGUNDA_VUELTA_YA_PROGRAMADA));
+            en_fecha(INICIO_SEGUNDA_VUELTA);
+            for (votante, candidato_id) in usuarios[3..].iter().zip([1, 1, 1, 1, 2, 2, 2, 2, 2]) {
+                como(*votante);
+                contrato.votar_a_candidato(2, candidato_id).unwrap();
+            }
+            en_fecha(FIN_SEGUNDA_VUELTA.checked_add(1).unwrap());
+            como(administrador());
+            contrato.finalizar_eleccion(2).unwrap();
+            assert_eq!(contrato.obtener_datos_eleccion_por_id(2).unwrap().ganador, Some(2));
+        }
 
-        /// Utilizado por un administrador.
-        /// Crea una elección colocando fecha de inicio y final.
-        #[ink(message)] //FUNCIONA
-        pub fn crear_eleccion(&mut self, fecha_inicial:String, fecha_final:String) -> Result<String, String>
-        {
-            if !self.es_administrador() { return Err(ERRORES::NO_ES_ADMINISTRADOR.to_string()); }
+        // ===================================================================================================
+        // =========================reparto D'Hondt===========================================================
+        // ===================================================================================================
+
+        fn resultado_lista(lista_id:u64, votos:u64, bancas:u64) -> ResultadoLista {
+            ResultadoLista { lista_id, votos, supera_umbral: true, bancas, electos: Vec::new() }
+        }
+
+        #[test]
+        fn cociente_mayor_compara_sin_redondeo() {
+            // 100 / 3 = 33.3 contra 50 / 2 = 25.
+            assert_eq!(TrabajoFinal::cociente_mayor(&resultado_lista(1, 100, 2), &resultado_lista(2, 50, 1)), Ok(true));
+            assert_eq!(TrabajoFinal::cociente_mayor(&resultado_lista(2, 50, 1), &resultado_lista(1, 100, 2)), Ok(false));
+        }
+
+        /// Con cocientes iguales gana la lista con más votos; si también empatan en votos, ninguna es mayor.
+        #[test]
+        fn cociente_mayor_ante_empate() {
+            assert_eq!(TrabajoFinal::cociente_mayor(&resultado_lista(1, 60, 1), &resultado_lista(2, 30, 0)), Ok(true));
+            assert_eq!(TrabajoFinal::cociente_mayor(&resultado_lista(2, 30, 0), &resultado_lista(1, 60, 1)), Ok(false));
+            assert_eq!(TrabajoFinal::cociente_mayor(&resultado_lista(1, 50, 0), &resultado_lista(2, 50, 0)), Ok(false));
+        }
+
+        /// Ejemplo clásico a escala: 8 bancas entre 10, 8, 3 y 2 votos quedan 4, 3, 1 y 0.
+        #[ink::test]
+        fn dhondt_reparto_clasico() {
+            let (contrato, eleccion_id) = eleccion_dhondt(8, 0, &[(10, 4), (8, 3), (3, 2), (2, 1)]);
+            let resultados = contrato.obtener_reparto_bancas(eleccion_id).unwrap();
+            assert_eq!(bancas_por_lista(&resultados), vec![4, 3, 1, 0]);
+            assert_eq!(resultados.iter().map(|resultado| resultado.votos).collect::<Vec<u64>>(), vec![10, 8, 3, 2]);
+            assert_eq!(resultados[0].electos, vec![1, 2, 3, 4]);
+            assert_eq!(resultados[1].electos, vec![5, 6, 7]);
+            assert_eq!(resultados[2].electos, vec![8]);
+            assert!(resultados.iter().all(|resultado| resultado.supera_umbral));
+        }
+
+        /// Con un umbral del 15% la tercera y cuarta lista quedan afuera. La última banca se disputa entre
+        /// 10 / 5 y 8 / 4, que empatan, y la gana la lista con más votos.
+        #[ink::test]
+        fn dhondt_umbral_y_empate_de_cocientes() {
+            let (contrato, eleccion_id) = eleccion_dhondt(8, 1_500, &[(10, 5), (8, 4), (3, 2), (2, 1)]);
+            let resultados = contrato.obtener_reparto_bancas(eleccion_id).unwrap();
+            assert_eq!(resultados.iter().map(|resultado| resultado.supera_umbral).collect::<Vec<bool>>(), vec![true, true, false, false]);
+            assert_eq!(bancas_por_lista(&resultados), vec![5, 3, 0, 0]);
+        }
+
+        /// Si las listas empatan en cociente y en votos, la banca va a la de menor id.
+        #[ink::test]
+        fn dhondt_empate_total_favorece_al_menor_id() {
+            let (contrato, eleccion_id) = eleccion_dhondt(3, 0, &[(2, 2), (2, 2)]);
+            let resultados = contrato.obtener_reparto_bancas(eleccion_id).unwrap();
+            assert_eq!(bancas_por_lista(&resultados), vec![2, 1]);
+        }
+
+        /// Una lista sin más candidatos cede la banca a la siguiente; si ninguna puede ocuparla, queda vacante.
+        #[ink::test]
+        fn dhondt_listas_sin_candidatos_suficientes() {
+            let (contrato, eleccion_id) = eleccion_dhondt(5, 0, &[(9, 1), (1, 2)]);
+            let resultados = contrato.obtener_reparto_bancas(eleccion_id).unwrap();
+            assert_eq!(bancas_por_lista(&resultados), vec![1, 2]);
+            assert_eq!(resultados[1].electos, vec![2, 3]);
+        }
+
+        // ===================================================================================================
+        // =========================escrutinio ponderado======================================================
+        // ===================================================================================================
 
-            let fecha_inicial_milisegundos = chrono::NaiveDateTime::parse_from_str(&fecha_inicial, "%d-%m-%Y %H:%M");
-            if fecha_inicial_milisegundos.is_err() {
-                return Err(String::from("Error en el formato de la fecha inicial. Formato: dd-mm-YYYY hh:mm"));
+        /// Elección ponderada finalizada de dos candidatos con los pesos indicados y los votos (candidato 1, candidato 2)
+        /// de cada claustro. Cada votante recibe su claustro antes de inscribirse.
+        fn eleccion_ponderada(claustros:&[(CLAUSTRO, u64, usize, usize)]) -> (TrabajoFinal, u64) {
+            let votantes = claustros.iter()
+                .try_fold(0usize, |total, (_, _, primero, segundo)| total.checked_add(*primero)?.checked_add(*segundo)).unwrap();
+            let (mut contrato, usuarios) = contrato_con_usuarios(u8::try_from(votantes.checked_add(2).unwrap()).unwrap());
+            let mut boletas = Vec::new();
+            for (claustro, _, votos_primero, votos_segundo) in claustros {
+                boletas.extend(core::iter::repeat_n((claustro.clone(), 1), *votos_primero));
+                boletas.extend(core::iter::repeat_n((claustro.clone(), 2), *votos_segundo));
             }
-            let fecha_final_milisegundos = chrono::NaiveDateTime::parse_from_str(&fecha_final, "%d-%m-%Y %H:%M");
-            if fecha_final_milisegundos.is_err() {
-                return Err(String::from("Error en el formato de la fecha final. Formato: dd-mm-YYYY hh:mm"));
+            for ((claustro, _), votante) in boletas.iter().zip(usuarios[2..].iter()) {
+                contrato.asignar_claustro(*votante, Some(claustro.clone())).unwrap();
             }
 
-            let eleccion_id_check = (self.elecciones.len() as u64).checked_add(1);
-            let eleccion_id:u64;
-            match eleccion_id_check {
-                Some(id_validado) => eleccion_id = id_validado,
-                None => return Err(String::from("Ocurrio un overflow al calcular la ID de la eleccion.")),
+            let ponderacion_claustros = claustros.iter().map(|(claustro, peso, _, _)| PesoClaustro { claustro: claustro.clone(), peso: *peso }).collect();
+            let configuracion = ConfiguracionEleccion { ponderacion_claustros, ..Default::default() };
+            let eleccion_id = eleccion_abierta(&mut contrato, configuracion, &usuarios[..2], &usuarios[2..]);
+            en_fecha(INICIO);
+            for ((_, candidato_id), votante) in boletas.iter().zip(usuarios[2..].iter()) {
+                como(*votante);
+                contrato.votar_a_candidato(eleccion_id, *candidato_id).unwrap();
             }
-            let eleccion = Eleccion {
-                id: eleccion_id,
-                candidatos: Vec::new(),
-                votantes: Vec::new(),
-                usuarios_pendientes: Vec::new(),
-                usuarios_rechazados: Vec::new(),
-                estado: ESTADO_ELECCION::CERRADA,
-                fecha_inicio: fecha_inicial_milisegundos.unwrap().and_utc().timestamp_millis() as u64,
-                fecha_final: fecha_final_milisegundos.unwrap().and_utc().timestamp_millis() as u64,
-            };
-            self.elecciones.push(eleccion);
+            finalizar(&mut contrato, eleccion_id);
+            (contrato, eleccion_id)
+        }
 
-            return Ok(String::from("Eleccion creada exitosamente. Id de la elección: ") + &eleccion_id.to_string());
+        fn fracciones(contrato:&TrabajoFinal, eleccion_id:u64) -> Vec<(u128, u128, u64)> {
+            contrato.obtener_resultado_ponderado(eleccion_id).unwrap().iter()
+                .map(|resultado| (resultado.numerador, resultado.denominador, resultado.porcentaje))
+                .collect()
         }
 
-        /// Utilizado por un administrador.
-        /// cierra una elección colocando su estado en CERRADO (estado anterior al INICIADA).
-        #[ink(message)] //FUNCIONA //MERJORAR
-        pub fn cerrar_eleccion(&mut self, eleccion_id: u64) -> Result<String, String>
-        {
-            if !self.es_administrador() { return Err(ERRORES::NO_ES_ADMINISTRADOR.to_string()); }
+        #[test]
+        fn mcd_y_mcm() {
+            assert_eq!(TrabajoFinal::mcd(48, 18), Ok(6));
+            assert_eq!(TrabajoFinal::mcd(7, 0), Ok(7));
+            assert_eq!(TrabajoFinal::mcm(4, 6), Ok(12));
+            assert_eq!(TrabajoFinal::mcm(3, 4), Ok(12));
+            assert_eq!(TrabajoFinal::mcm(1, 7), Ok(7));
+            assert_eq!(TrabajoFinal::mcm(12, 12), Ok(12));
+        }
 
-            let block_timestamp = self.env().block_timestamp();
-            let eleccion_option = self.obtener_eleccion_por_id(eleccion_id);
-            match eleccion_option {
-                Some(eleccion) => {
-                    if eleccion.fecha_inicio < block_timestamp {
-                        return Err(String::from("La eleccion se encuentra en la fecha de votacion!"));
-                    }
-                    match eleccion.estado {
-                        ESTADO_ELECCION::CERRADA => Err(String::from("La eleccion ya se encuantra en el estado correspondiente!")),
-                        ESTADO_ELECCION::INICIADA => Err(String::from("La eleccion se encuentra en el estado de votacion!")),
-                        ESTADO_ELECCION::FINALIZADA => Err(String::from("La eleccion ya cerro!")),
-                        _ => {  
-                            eleccion.estado = ESTADO_ELECCION::CERRADA;
-                            Ok(String::from("Eleccion CERRADA exitosamente. Id de la elección: ") + &eleccion.id.to_string())
-                        }
-                    }
-                },
-                None => Err(String::from("La eleccion enviada no existe!")),
-            }
+        /// Docentes (peso 3) votan 2 a 1 y estudiantes (peso 2) 2 a 2. Sobre mcm 12 y pesos 5:
+        /// el candidato 1 obtiene (3 * 2/3 + 2 * 2/4) / 5 = 36/60 y el candidato 2, 24/60.
+        #[ink::test]
+        fn ponderado_con_ganador() {
+            let (contrato, eleccion_id) = eleccion_ponderada(&[(CLAUSTRO::DOCENTES, 3, 2, 1), (CLAUSTRO::ESTUDIANTES, 2, 2, 2)]);
+            assert_eq!(fracciones(&contrato, eleccion_id), vec![(36, 60, 6_000), (24, 60, 4_000)]);
+            assert_eq!(ganador(&contrato, eleccion_id), Some(1));
         }
-        /// Utilizado por un administrador.
-        /// cierra una elección colocando su estado en CERRADO (estado anterior al INICIADA).
-        #[ink(message)] //FUNCIONA //MERJORAR
-        pub fn abrir_eleccion(&mut self, eleccion_id: u64) -> Result<String, String>
-        {
-            if !self.es_administrador() { return Err(ERRORES::NO_ES_ADMINISTRADOR.to_string()); }
 
-            let block_timestamp = self.env().block_timestamp();
-            let eleccion_option = self.obtener_eleccion_por_id(eleccion_id);
-            match eleccion_option {
-                Some(eleccion) => {
-                    if eleccion.fecha_inicio < block_timestamp {
-                        return Err(String::from("La eleccion se encuentra en la fecha de votacion!"));
-                    }
-                    match eleccion.estado {
-                        ESTADO_ELECCION::ABIERTA => Err(String::from("La eleccion ya se encuentra en el estado correspondiente!")),
-                        ESTADO_ELECCION::INICIADA => Err(String::from("La eleccion se encuentra en el estado de votacion!")),
-                        ESTADO_ELECCION::FINALIZADA => Err(String::from("La eleccion ya cerro!")),
-                        _ => {  
-                            eleccion.estado = ESTADO_ELECCION::ABIERTA;
-                            Ok(String::from("Eleccion ABIERTA exitosamente. Id de la elección: ") + &eleccion.id.to_string())
-                        }
-                    }
-                },
-                None => Err(String::from("La eleccion enviada no existe!")),
-            }
+        /// Un claustro más numeroso no pesa más: con estudiantes 1 a 3 ambos candidatos obtienen 30/60.
+        #[ink::test]
+        fn ponderado_empate_sin_ganador() {
+            let (contrato, eleccion_id) = eleccion_ponderada(&[(CLAUSTRO::DOCENTES, 3, 2, 1), (CLAUSTRO::ESTUDIANTES, 2, 1, 3)]);
+            assert_eq!(fracciones(&contrato, eleccion_id), vec![(30, 60, 5_000), (30, 60, 5_000)]);
+            assert_eq!(ganador(&contrato, eleccion_id), None);
         }
-        /// Utilizado por un administrador.
-        /// cierra una elección colocando su estado en CERRADO (estado anterior al INICIADA).
-        #[ink(message)]
-        pub fn iniciar_eleccion(&mut self, eleccion_id: u64) -> Result<String, String>
-        {
-            if !self.es_administrador() { return Err(ERRORES::NO_ES_ADMINISTRADOR.to_string()); }
 
-            let block_timestamp = self.env().block_timestamp();
-            let eleccion_option = self.obtener_eleccion_por_id(eleccion_id);
-            match eleccion_option {
-                Some(eleccion) => {
-                    if eleccion.fecha_inicio > block_timestamp {
-                        return Err(String::from("No es la fecha propuesta, no puedes iniciarla."));
-                    }
-                    if eleccion.fecha_final < block_timestamp {
-                        return Err(String::from("Ya finalizo, no puedes iniciarla."));
-                    }
-                    match eleccion.estado {
-                        ESTADO_ELECCION::INICIADA => Err(String::from("La eleccion ya se encuentra en el estado correspondiente!")),
-                        ESTADO_ELECCION::FINALIZADA => Err(String::from("La eleccion ya cerro!")),
-                        _ => {  
-                            eleccion.estado = ESTADO_ELECCION::INICIADA;
-                            Ok(String::from("Eleccion INICIADA exitosamente. Id de la elección: ") + &eleccion.id.to_string())
-                        }
-                    }
-                },
-                None => Err(String::from("La eleccion enviada no existe!")),
-            }
+        /// El peso de un claustro sin votos válidos no integra la suma.
+        #[ink::test]
+        fn ponderado_ignora_claustros_sin_votos() {
+            let (contrato, eleccion_id) = eleccion_ponderada(&[
+                (CLAUSTRO::DOCENTES, 3, 2, 1),
+                (CLAUSTRO::GRADUADOS, 5, 0, 0),
+                (CLAUSTRO::ESTUDIANTES, 2, 2, 2),
+            ]);
+            assert_eq!(fracciones(&contrato, eleccion_id), vec![(36, 60, 6_000), (24, 60, 4_000)]);
+            assert_eq!(ganador(&contrato, eleccion_id), Some(1));
         }
-        /// Utilizado por un administrador.
-        /// cierra una elección colocando su estado en CERRADO (estado anterior al INICIADA).
-        #[ink(message)]
-        pub fn finalizar_eleccion(&mut self, eleccion_id: u64) -> Result<String, String>
-        {
-            if !self.es_administrador() { return Err(ERRORES::NO_ES_ADMINISTRADOR.to_string()); }
 
-            let block_timestamp = self.env().block_timestamp();
-            let eleccion_option = self.obtener_eleccion_por_id(eleccion_id);
-            match eleccion_option {
-                Some(eleccion) => {
-                    if eleccion.fecha_final > block_timestamp {
-                        return Err(String::from("No es la fecha propuesta, no puedes finalizarla."));
-                    }
-                    match eleccion.estado {
-                        ESTADO_ELECCION::FINALIZADA => Err(String::from("La eleccion ya se encuantra en el estado correspondiente!")),
-                        _ => {  
-                            eleccion.estado = ESTADO_ELECCION::FINALIZADA;
-                            Ok(String::from("Eleccion FINALIZADA exitosamente. Id de la elección: ") + &eleccion.id.to_string())
-                        }
-                    }
-                },
-                None => Err(String::from("La eleccion enviada no existe!")),
+        /// Con pesos 1, 1 y 4 y dos claustros de 10^10 votantes, el escrutinio necesita
+        /// 10.000 * 6 * 10^20 * graduados <= u128::MAX. Se acepta al graduado que deja el claustro justo en el
+        /// límite y se rechaza al siguiente, y la elección se sigue pudiendo escrutar.
+        #[ink::test]
+        fn ponderado_rechaza_votantes_que_desbordarian_el_escrutinio() {
+            let (mut contrato, usuarios) = contrato_con_usuarios(3);
+            for usuario in usuarios[1..].iter() {
+                contrato.asignar_claustro(*usuario, Some(CLAUSTRO::GRADUADOS)).unwrap();
+            }
+            let ponderacion_claustros = vec![
+                PesoClaustro { claustro: CLAUSTRO::DOCENTES, peso: 1 },
+                PesoClaustro { claustro: CLAUSTRO::ESTUDIANTES, peso: 1 },
+                PesoClaustro { claustro: CLAUSTRO::GRADUADOS, peso: 4 },
+            ];
+            let configuracion = ConfiguracionEleccion { ponderacion_claustros, ..Default::default() };
+            let eleccion_id = eleccion_abierta(&mut contrato, configuracion, &usuarios[..1], &[]);
+
+            // Padrones que no se pueden armar en una prueba: se cargan los contadores directamente.
+            let limite_graduados = u64::try_from(u128::MAX.checked_div(6_000_000_000_000_000_000_000_000).unwrap()).unwrap();
+            contrato.votantes_por_claustro.insert((eleccion_id, CLAUSTRO::DOCENTES), &10_000_000_000);
+            contrato.votantes_por_claustro.insert((eleccion_id, CLAUSTRO::ESTUDIANTES), &10_000_000_000);
+            contrato.votantes_por_claustro.insert((eleccion_id, CLAUSTRO::GRADUADOS), &limite_graduados.checked_sub(1).unwrap());
+            for usuario in usuarios[1..].iter() {
+                como(*usuario);
+                contrato.inscribir_usuario_en_eleccion(eleccion_id, TIPO_DE_USUARIO::VOTANTE).unwrap();
             }
+            como(administrador());
+            assert_eq!(contrato.procesar_usuarios_en_una_eleccion(eleccion_id, true), Ok(usuarios[1]));
+            assert_eq!(contrato.procesar_usuarios_en_una_eleccion(eleccion_id, true), Err(Error::LIMITE_PONDERACION_EXCEDIDO));
+            assert_eq!(contrato.obtener_datos_eleccion_por_id(eleccion_id).unwrap().cantidad_votantes, 1);
+
+            en_fecha(INICIO);
+            como(usuarios[1]);
+            contrato.votar_a_candidato(eleccion_id, 1).unwrap();
+            finalizar(&mut contrato, eleccion_id);
+            assert_eq!(fracciones(&contrato, eleccion_id), vec![(4, 4, 10_000)]);
+            assert_eq!(ganador(&contrato, eleccion_id), Some(1));
         }
 
-        #[ink(message)] //FUNCIONA
-        pub fn obtener_ids_elecciones(&self) -> Vec<u64>
-        {
-            self.elecciones.iter().map(|eleccion| eleccion.id).collect()
+        // ===================================================================================================
+        // =========================delegación de votos=======================================================
+        // ===================================================================================================
+
+        /// Elección con delegación, los candidatos 1 y 2 y `votantes` votantes, ya en votación.
+        fn eleccion_con_delegacion(votantes:u8) -> (TrabajoFinal, u64, Vec<AccountId>) {
+            let (mut contrato, usuarios) = contrato_con_usuarios(votantes.checked_add(2).unwrap());
+            let configuracion = ConfiguracionEleccion { permite_delegacion: true, ..Default::default() };
+            let eleccion_id = eleccion_abierta(&mut contrato, configuracion, &usuarios[..2], &usuarios[2..]);
+            en_fecha(INICIO);
+            (contrato, eleccion_id, usuarios[2..].to_vec())
+        }
+        /// Finaliza la elección y devuelve los votos de los candidatos 1 y 2.
+        fn votos_finales(contrato:&mut TrabajoFinal, eleccion_id:u64) -> Vec<u64> {
+            finalizar(contrato, eleccion_id);
+            contrato.obtener_candidatos_eleccion_por_id(eleccion_id).unwrap().iter().map(|candidato| candidato.votos_totales).collect()
         }
 
-        #[ink(message)] //FUNCIONA
-        pub fn obtener_datos_eleccion_por_id(&mut self, eleccion_id: u64) -> Result<String, String>
-        {
-            // if !self.es_administrador() { return Err(ERRORES::NO_ES_ADMINISTRADOR.to_string()); }
-            let eleccion_option = self.obtener_eleccion_por_id(eleccion_id);
-            match eleccion_option {
-                Some(eleccion) => {
-                    let mut str = String::from("ID: ") + &eleccion.id.to_string();
-                    match eleccion.estado {
-                        ESTADO_ELECCION::CERRADA => str.push_str("\nEstado: CERRADA"),
-                        ESTADO_ELECCION::ABIERTA => str.push_str("\nEstado: ABIERTA"),
-                        ESTADO_ELECCION::INICIADA => str.push_str("\nEstado: INICIADA"),
-                        ESTADO_ELECCION::FINALIZADA => str.push_str("\nEstado: FINALIZADA"),
-                    }
-                    str.push_str((String::from("\nfecha_inicio: ") + &eleccion.fecha_inicio.to_string()).as_str());
-                    str.push_str((String::from("\nfecha_final: ") + &eleccion.fecha_final.to_string()).as_str());
-                    Ok(str)
-                    // Ok(String::from("Id de la elección: ") + &eleccion.id.to_string())
-                },
-                None => Err(String::from("La eleccion enviada no existe!")),
+        /// A delega en B y B en C: el voto de C cuenta por los tres y A y B ya no pueden votar.
+        #[ink::test]
+        fn delegacion_en_cadena() {
+            let (mut contrato, eleccion_id, votantes) = eleccion_con_delegacion(4);
+            como(votantes[0]);
+            contrato.delegar_voto(eleccion_id, votantes[1]).unwrap();
+            como(votantes[1]);
+            contrato.delegar_voto(eleccion_id, votantes[2]).unwrap();
+            como(votantes[2]);
+            contrato.votar_a_candidato(eleccion_id, 1).unwrap();
+            como(votantes[3]);
+            contrato.votar_a_candidato(eleccion_id, 2).unwrap();
+
+            for votante in &votantes[..2] {
+                como(*votante);
+                assert_eq!(contrato.votar_a_candidato(eleccion_id, 2), Err(Error::YA_VOTO));
             }
+            assert_eq!(votos_finales(&mut contrato, eleccion_id), vec![3, 1]);
+            assert_eq!(contrato.obtener_datos_eleccion_por_id(eleccion_id).unwrap().votos_emitidos, 4);
+            assert_eq!(contrato.obtener_votos_delegados(eleccion_id, 0, 10), Ok(vec![VotosDelegados { delegado: votantes[2], votos: 2 }]));
+            assert_eq!(contrato.obtener_delegacion(eleccion_id, votantes[0]), Some(votantes[1]));
         }
-        
-        #[ink(message)] //FUNCIONA
-        pub fn obtener_candidatos_eleccion_por_id(&mut self, eleccion_id: u64) -> Result<String, String>
-        {
-            // if !self.es_administrador() { return Err(ERRORES::NO_ES_ADMINISTRADOR.to_string()); }
-            let eleccion_option = self.obtener_eleccion_por_id(eleccion_id);
-            match eleccion_option {
-                Some(eleccion) => {
-                    if eleccion.votacion_abierta() { return Err(String::from("La eleccion no finalizo aun!")) };
-                    let mut str = String::from("ID: ") + &eleccion.id.to_string();
-                    eleccion.candidatos.iter().for_each(|candidato| {
-                        str.push_str((String::from("\n\n")).as_str());
-                        str.push_str((String::from("\nId candidato: ") + &candidato.id.to_string()).as_str());
-                        str.push_str((String::from("\nId usuario: ") + hex::encode(&candidato.usuario_id).as_str() ).as_str());
-                        str.push_str((String::from("\nVotos recibidos: ") + &candidato.votos_totales.to_string()).as_str());
-                    });
-                    Ok(str)
-                    // Ok(String::from("Id de la elección: ") + &eleccion.id.to_string())
-                },
-                None => Err(String::from("La eleccion enviada no existe!")),
-            }
+
+        /// No se puede delegar en uno mismo ni cerrar un ciclo, directo o a través de una cadena.
+        #[ink::test]
+        fn delegacion_circular_se_rechaza() {
+            let (mut contrato, eleccion_id, votantes) = eleccion_con_delegacion(3);
+            como(votantes[0]);
+            assert_eq!(contrato.delegar_voto(eleccion_id, votantes[0]), Err(Error::DELEGACION_CIRCULAR));
+            contrato.delegar_voto(eleccion_id, votantes[1]).unwrap();
+            como(votantes[1]);
+            assert_eq!(contrato.delegar_voto(eleccion_id, votantes[0]), Err(Error::DELEGACION_CIRCULAR));
+            contrato.delegar_voto(eleccion_id, votantes[2]).unwrap();
+            como(votantes[2]);
+            assert_eq!(contrato.delegar_voto(eleccion_id, votantes[0]), Err(Error::DELEGACION_CIRCULAR));
+            assert_eq!(contrato.obtener_delegacion(eleccion_id, votantes[2]), None);
+
+            contrato.votar_a_candidato(eleccion_id, 1).unwrap();
+            assert_eq!(votos_finales(&mut contrato, eleccion_id), vec![3, 0]);
         }
-        #[ink(message)] //FUNCIONA
-        pub fn obtener_votantes_eleccion_por_id(&mut self, eleccion_id: u64) -> Result<String, String>
-        {
-            // if !self.es_administrador() { return Err(ERRORES::NO_ES_ADMINISTRADOR.to_string()); }
-            let eleccion_option = self.obtener_eleccion_por_id(eleccion_id);
-            match eleccion_option {
-                Some(eleccion) => {
-                    if eleccion.votacion_abierta() { return Err(String::from("La eleccion no finalizo aun!")) };
-                    let mut str = String::from("ID: ") + &eleccion.id.to_string();
-                    eleccion.votantes.iter().for_each(|votante| {
-                        str.push_str((String::from("\n\n")).as_str());
-                        str.push_str((String::from("\nId usuario: ") + hex::encode(&votante.usuario_id).as_str() ).as_str());
-                        str.push_str((String::from("\nVotos emitido: ") + &votante.voto_emitido.to_string() ).as_str());
-                    });
-                    Ok(str)
-                    // Ok(String::from("Id de la elección: ") + &eleccion.id.to_string())
-                },
-                None => Err(String::from("La eleccion enviada no existe!")),
-            }
+
+        /// Quien revoca su delegación antes de que vote el delegado vuelve a votar por sí mismo.
+        #[ink::test]
+        fn revocar_la_delegacion_antes_de_votar() {
+            let (mut contrato, eleccion_id, votantes) = eleccion_con_delegacion(2);
+            como(votantes[0]);
+            assert_eq!(contrato.revocar_delegacion(eleccion_id), Err(Error::NO_HAY_DELEGACION));
+            contrato.delegar_voto(eleccion_id, votantes[1]).unwrap();
+            contrato.revocar_delegacion(eleccion_id).unwrap();
+            assert_eq!(contrato.obtener_delegacion(eleccion_id, votantes[0]), None);
+
+            como(votantes[1]);
+            contrato.votar_a_candidato(eleccion_id, 1).unwrap();
+            como(votantes[0]);
+            contrato.votar_a_candidato(eleccion_id, 2).unwrap();
+            assert_eq!(votos_finales(&mut contrato, eleccion_id), vec![1, 1]);
+            assert_eq!(contrato.obtener_votos_delegados(eleccion_id, 0, 10), Ok(Vec::new()));
         }
 
-    // ====================================================================
-    // ====================================================================
-    // ====================================================================
+        /// Votar después de delegar deja sin efecto la delegación, y el delegado vota solo por sí mismo.
+        #[ink::test]
+        fn votar_despues_de_delegar() {
+            let (mut contrato, eleccion_id, votantes) = eleccion_con_delegacion(2);
+            como(votantes[0]);
+            contrato.delegar_voto(eleccion_id, votantes[1]).unwrap();
+            contrato.votar_a_candidato(eleccion_id, 2).unwrap();
+            assert_eq!(contrato.obtener_delegacion(eleccion_id, votantes[0]), None);
 
-    // ====================================================================
-    // ===================Eleccion: usuarios===============================
-    // ====================================================================
-        /// Utilizado por un Administrador.
-        /// Obtiene la información del próximo usuario a registrarse.
-        #[ink(message)] //FUNCIONA
-        pub fn obtener_siguiente_usuario_pendiente_en_una_eleccion(&mut self, eleccion_id:u64) -> Result<String, String>
-        {
-            if !self.es_administrador() { return Err(ERRORES::NO_ES_ADMINISTRADOR.to_string()); }
-            let eleccion_elegida = match self.obtener_eleccion_por_id(eleccion_id) {
-                Some(eleccion) => eleccion,
-                None => return Err(String::from("Eleccion no encontrada")),
-            };
-            let sig_usuario = eleccion_elegida.usuarios_pendientes.first();
-            match sig_usuario {
-                Some(usuario_eleccion) => {
-                    let mut datos_usuario = String::from("Usuario: ");
-                    datos_usuario.push_str( hex::encode(usuario_eleccion.0).as_str() );
-                    match usuario_eleccion.1 {
-                        TIPO_DE_USUARIO::VOTANTE => datos_usuario.push_str("\nEl usuario quiere ser un VOTANTE"),
-                        TIPO_DE_USUARIO::CANDIDATO => datos_usuario.push_str("\nEl usuario quiere ser un CANDIDATO")
-                    };
-                    Ok(datos_usuario)
-                },
-                None => Err(String::from("No hay usuarios pendientes.")),
+            como(votantes[1]);
+            contrato.votar_a_candidato(eleccion_id, 1).unwrap();
+            assert_eq!(votos_finales(&mut contrato, eleccion_id), vec![1, 1]);
+            assert_eq!(contrato.obtener_datos_eleccion_por_id(eleccion_id).unwrap().votos_emitidos, 2);
+            assert_eq!(contrato.obtener_votos_delegados(eleccion_id, 0, 10), Ok(Vec::new()));
+        }
+
+        /// Si el delegado nunca vota, los votos delegados en él no se cuentan.
+        #[ink::test]
+        fn delegado_que_no_vota() {
+            let (mut contrato, eleccion_id, votantes) = eleccion_con_delegacion(3);
+            como(votantes[0]);
+            contrato.delegar_voto(eleccion_id, votantes[1]).unwrap();
+            como(votantes[2]);
+            contrato.votar_a_candidato(eleccion_id, 1).unwrap();
+
+            assert_eq!(votos_finales(&mut contrato, eleccion_id), vec![1, 0]);
+            assert_eq!(contrato.obtener_datos_eleccion_por_id(eleccion_id).unwrap().votos_emitidos, 1);
+            assert_eq!(contrato.obtener_votos_delegados(eleccion_id, 0, 10), Ok(Vec::new()));
+        }
+
+        // ===================================================================================================
+        // =========================voto por aprobación=======================================================
+        // ===================================================================================================
+
+        /// Elección APROBACION finalizada de tres candidatos, hasta dos aprobaciones por boleta, con las boletas
+        /// indicadas seguidas de `blancos` votos en blanco y `nulos` votos nulos.
+        fn eleccion_aprobacion(blancos_en_base:bool, boletas:&[&[u64]], blancos:usize, nulos:usize) -> (TrabajoFinal, u64) {
+            let votantes = boletas.len().checked_add(blancos).unwrap().checked_add(nulos).unwrap();
+            let (mut contrato, usuarios) = contrato_con_usuarios(u8::try_from(votantes.checked_add(3).unwrap()).unwrap());
+            let sistema_escrutinio = SISTEMA_ESCRUTINIO::APROBACION { maximo_aprobaciones: Some(2) };
+            let configuracion = ConfiguracionEleccion { sistema_escrutinio, blancos_en_base, ..Default::default() };
+            let eleccion_id = eleccion_abierta(&mut contrato, configuracion, &usuarios[..3], &usuarios[3..]);
+            en_fecha(INICIO);
+            let mut votantes = usuarios[3..].iter();
+            for (aprobados, votante) in boletas.iter().zip(votantes.by_ref()) {
+                como(*votante);
+                contrato.votar_aprobaciones(eleccion_id, aprobados.to_vec()).unwrap();
+            }
+            for votante in votantes.by_ref().take(blancos) {
+                como(*votante);
+                contrato.votar_en_blanco(eleccion_id).unwrap();
+            }
+            for votante in votantes {
+                como(*votante);
+                contrato.votar_nulo(eleccion_id).unwrap();
             }
+            finalizar(&mut contrato, eleccion_id);
+            (contrato, eleccion_id)
+        }
+        fn aprobaciones(contrato:&TrabajoFinal, eleccion_id:u64) -> Vec<(u64, u64)> {
+            contrato.obtener_resultado_aprobacion(eleccion_id).unwrap().iter()
+                .map(|resultado| (resultado.aprobaciones, resultado.porcentaje))
+                .collect()
         }
-        /// Utilizado por un Administrador.
-        /// Se procesará el próximo usuario pendiente en una eleccion particular.
-        /// y se lo coloca en el vector de candidato o votante en esa eleccion segun que quiera ser.
-        #[ink(message)] //FUNCIONA
-        pub fn procesar_usuarios_en_una_eleccion(&mut self, eleccion_id:u64,aceptar_usuario:bool) -> Result<String, String>
-        {
-                if !self.es_administrador() { return Err(ERRORES::NO_ES_ADMINISTRADOR.to_string()); }
 
-               let eleccion_elegida = match self.obtener_eleccion_por_id(eleccion_id) {
-                Some(eleccion) => eleccion,
-                None => return Err(String::from("Eleccion no encontrada")),
-            };
-            eleccion_elegida.procesar_siguiente_usuario_pendiente(aceptar_usuario)
+        /// Tres boletas con seis aprobaciones: cada porcentaje es sobre las tres boletas, así que suman más de 100%.
+        /// Los votos en blanco y nulos no cuentan como boletas.
+        #[ink::test]
+        fn aprobacion_porcentajes_sobre_las_boletas() {
+            let (contrato, eleccion_id) = eleccion_aprobacion(false, &[&[1, 2], &[2, 1], &[1, 3]], 1, 1);
+            assert_eq!(aprobaciones(&contrato, eleccion_id), vec![(3, 10_000), (2, 6_666), (1, 3_333)]);
+            assert_eq!(ganador(&contrato, eleccion_id), Some(1));
         }
 
+        /// Con los blancos en la base el voto en blanco se suma a las tres boletas; los nulos siguen afuera.
+        #[ink::test]
+        fn aprobacion_con_los_blancos_en_la_base() {
+            let (contrato, eleccion_id) = eleccion_aprobacion(true, &[&[1, 2], &[2, 1], &[1, 3]], 1, 1);
+            assert_eq!(aprobaciones(&contrato, eleccion_id), vec![(3, 7_500), (2, 5_000), (1, 2_500)]);
+            assert_eq!(ganador(&contrato, eleccion_id), Some(1));
+        }
 
+        /// Sin boletas la base es cero y los porcentajes quedan en cero, sin ganador.
+        #[ink::test]
+        fn aprobacion_sin_boletas() {
+            let (contrato, eleccion_id) = eleccion_aprobacion(false, &[], 2, 0);
+            assert_eq!(aprobaciones(&contrato, eleccion_id), vec![(0, 0), (0, 0), (0, 0)]);
+            assert_eq!(ganador(&contrato, eleccion_id), None);
+        }
 
-        // inscribir_usuario_en_eleccion (deben ser usuarios del sistema  y no estar ya en la eleccion)
-        #[ink(message)] //FUNCIONA
-        pub fn inscribir_usuario_en_eleccion(&mut self, eleccion_id:u64, tipo:TIPO_DE_USUARIO) -> Result<String, String>
-        {
-            // es usuario valido en el sistema (no esta pendiente de aprobacion y no esta rechazado)
-            let id = self.env().caller();
-            if !self.es_usuario_registrado(id) { return Err(ERRORES::USUARIO_NO_REGISTRADO.to_string()); }
+        #[ink::test]
+        fn aprobacion_valida_la_boleta() {
+            let (mut contrato, usuarios) = contrato_con_usuarios(4);
+            let sistema_escrutinio = SISTEMA_ESCRUTINIO::APROBACION { maximo_aprobaciones: Some(2) };
+            let configuracion = ConfiguracionEleccion { sistema_escrutinio, ..Default::default() };
+            let eleccion_id = eleccion_abierta(&mut contrato, configuracion, &usuarios[..3], &usuarios[3..]);
+            en_fecha(INICIO);
+            como(usuarios[3]);
+            assert_eq!(contrato.votar_aprobaciones(eleccion_id, Vec::new()), Err(Error::BOLETA_VACIA));
+            assert_eq!(contrato.votar_aprobaciones(eleccion_id, vec![1, 2, 3]), Err(Error::DEMASIADAS_APROBACIONES));
+            assert_eq!(contrato.votar_aprobaciones(eleccion_id, vec![2, 2]), Err(Error::CANDIDATO_REPETIDO));
+            assert_eq!(contrato.votar_aprobaciones(eleccion_id, vec![4]), Err(Error::CANDIDATO_NO_EXISTE));
+            assert_eq!(contrato.votar_a_candidato(eleccion_id, 1), Err(Error::MODO_VOTACION_INCORRECTO));
+            contrato.votar_aprobaciones(eleccion_id, vec![3]).unwrap();
+        }
 
-            // el estado de la eleccion es ABIERTA
-            let block_timestamp = self.env().block_timestamp();
-            let result = self.validar_estado_eleccion_para_inscripciones(eleccion_id, block_timestamp);
-            let eleccion = match result {
-                Ok(eleccion) => eleccion,
-                Err(mensaje) => return Err(mensaje)
-            };
+        // ===================================================================================================
+        // =========================método de Schulze=========================================================
+        // ===================================================================================================
 
-            //Validar que un usuario que ya ha sido rechazado en la misma eleccion no intente volver a ponerse como pendiente 
-            if eleccion.es_usuario_rechazado(id) {  return Err(String::from("Tu solicitud de registro ya fue rechazada.")); }
-            if eleccion.es_usuario_pendiente(id) { return Err(String::from("Ya estás en la cola de usuarios pendientes.")); }
-            if eleccion.es_votante(id) { return Err(String::from("Ya estás registrado como votante."));}
-            if eleccion.es_candidato(id) {return Err("Ya has sido aceptado como candidato".to_string());}
-            
-            eleccion.usuarios_pendientes.push((id,tipo));
-            return Ok("Ingresó a la elección correctamente Pendiente de aprobacion del Administrador".to_string());
+        /// Ejemplo clásico de 45 votantes y 5 candidatos (A a E con ids 1 a 5). Se verifican la matriz de
+        /// preferencias, la de caminos más fuertes y que gana E aunque no es ganador de Condorcet.
+        #[ink::test]
+        fn schulze_ejemplo_de_45_votantes() {
+            let (contrato, eleccion_id) = eleccion_con_boletas(SISTEMA_ESCRUTINIO::SCHULZE, 5, &[
+                (5, &[1, 3, 2, 5, 4]),
+                (5, &[1, 4, 5, 3, 2]),
+                (8, &[2, 5, 4, 1, 3]),
+                (3, &[3, 1, 2, 5, 4]),
+                (7, &[3, 1, 5, 2, 4]),
+                (2, &[3, 2, 1, 4, 5]),
+                (7, &[4, 3, 5, 2, 1]),
+                (8, &[5, 2, 1, 4, 3]),
+            ]);
+            assert_eq!(contrato.obtener_matriz_preferencias(eleccion_id), Ok(vec![
+                vec![0, 20, 26, 30, 22],
+                vec![25, 0, 16, 33, 18],
+                vec![19, 29, 0, 17, 24],
+                vec![15, 12, 28, 0, 14],
+                vec![23, 27, 21, 31, 0],
+            ]));
+            assert_eq!(contrato.obtener_caminos_mas_fuertes(eleccion_id), Ok(vec![
+                vec![0, 28, 28, 30, 24],
+                vec![25, 0, 28, 33, 24],
+                vec![25, 29, 0, 29, 24],
+                vec![25, 28, 28, 0, 24],
+                vec![25, 28, 28, 31, 0],
+            ]));
+            assert_eq!(ganador(&contrato, eleccion_id), Some(5));
         }
 
+        /// Un ciclo de Condorcet con todas las preferencias de igual fuerza deja a todos empatados.
+        #[ink::test]
+        fn schulze_ciclo_simetrico_sin_ganador() {
+            let (contrato, eleccion_id) = eleccion_con_boletas(SISTEMA_ESCRUTINIO::SCHULZE, 3, &[(1, &[1, 2, 3]), (1, &[2, 3, 1]), (1, &[3, 1, 2])]);
+            assert_eq!(contrato.obtener_caminos_mas_fuertes(eleccion_id), Ok(vec![vec![0, 2, 2], vec![2, 0, 2], vec![2, 2, 0]]));
+            assert_eq!(ganador(&contrato, eleccion_id), None);
+        }
 
-        /// Utilizado por los usuarios registrados en el sistema y que están en la elección ingresada.
-        /// Se utiliza para poder obtener información de algún candidato en específico.
-        /// Las IDs de los candidatos van de 1 a N.
-        #[ink(message)] //FUNCIONA
-        pub fn obtener_informacion_candidato(&mut self, eleccion_id: u64, candidato_id: u64) -> Result<String, String> {
-            let eleccion_elegida = self.obtener_eleccion_por_id(eleccion_id)
-                .ok_or_else(|| String::from("No existe una elección con ese id."))?;
-            
-            let candidato_elegido = eleccion_elegida.obtener_informacion_candidato(candidato_id)
-                .ok_or_else(|| String::from("No existe un candidato con ese id."))?;
-            
-            let usuario_id = candidato_elegido.usuario_id;
-            let usuario = self.obtener_usuario_por_id(usuario_id)
-                .ok_or_else(|| String::from("No existe un usuario/candidato con ese id."))?;
-        
-            let informacion = format!("Nombre: {}\nApellido: {}", usuario.nombre, usuario.apellido);
-            
-            Ok(informacion)
+        #[ink::test]
+        fn schulze_empate_entre_dos_sin_ganador() {
+            let (contrato, eleccion_id) = eleccion_con_boletas(SISTEMA_ESCRUTINIO::SCHULZE, 2, &[(2, &[1, 2]), (2, &[2])]);
+            assert_eq!(ganador(&contrato, eleccion_id), None);
         }
 
-        /// Utilizado por los usuarios registrados en el sistema y que están en la elección como votantes.
-        /// Si el usuario ya emitió su voto, no puede volver a votar en la misma elección.
-        /// Si el usuario no es votante, no puede votar.
-        /// Si el periodo de la votación no comenzó o terminó, no puede votar.
-        #[ink(message)] 
-        pub fn votar_a_candidato(&mut self, eleccion_id:u64, candidato_id:u64) -> Result<String, String>
-        {
-            // es usuario valido en el sistema (no esta pendiente de aprobacion y no esta rechazado)
-            let id = self.env().caller();
-            if !self.es_usuario_registrado(id) { return Err(ERRORES::USUARIO_NO_REGISTRADO.to_string()); }
+        /// Una matriz guardada antes de que se sumara un candidato se amplía con ceros. Como nadie votó en contra
+        /// del nuevo candidato, empata con el 1 y no hay ganador.
+        #[ink::test]
+        fn schulze_matriz_anterior_a_un_candidato() {
+            let (mut contrato, mut eleccion) = eleccion_con_candidatos(SISTEMA_ESCRUTINIO::SCHULZE, 2);
+            contrato.sumar_boleta_a_matriz(&eleccion, &[1, 2]).unwrap();
+            eleccion.cantidad_boletas = 1;
+            contrato.agregar_candidato(&mut eleccion, cuenta(3), 0).unwrap();
+            assert_eq!(contrato.obtener_matriz_pares(&eleccion), vec![vec![0, 1, 0], vec![0, 0, 0], vec![0, 0, 0]]);
+            assert_eq!(contrato.escrutar_schulze(&eleccion).unwrap().1, None);
+        }
 
-            // el estado de la eleccion es ABIERTA
-            let block_timestamp = self.env().block_timestamp();
-            let result = self.validar_estado_eleccion_para_votaciones(eleccion_id, block_timestamp);
-            let eleccion = match result {
-                Ok(eleccion) => eleccion,
-                Err(mensaje) => return Err(mensaje)
-            };
+        /// Un candidato cuya inscripción sigue pendiente al empezar la votación ya no puede aceptarse,
+        /// y el escrutinio de la elección termina con normalidad.
+        #[ink::test]
+        fn candidato_pendiente_no_se_acepta_con_la_votacion_iniciada() {
+            let (mut contrato, usuarios) = contrato_con_usuarios(4);
+            let configuracion = ConfiguracionEleccion { sistema_escrutinio: SISTEMA_ESCRUTINIO::SCHULZE, ..Default::default() };
+            let eleccion_id = eleccion_abierta(&mut contrato, configuracion, &usuarios[..2], &usuarios[3..]);
+            como(usuarios[2]);
+            contrato.inscribir_usuario_en_eleccion(eleccion_id, TIPO_DE_USUARIO::CANDIDATO).unwrap();
 
-            //Validar que un usuario que ya ha sido rechazado en la misma eleccion no intente volver a ponerse como pendiente 
-            if eleccion.es_usuario_rechazado(id) {  return Err(String::from("Tu solicitud de registro fue rechazada.")); }
-            if eleccion.es_usuario_pendiente(id) { return Err(String::from("Estás en la cola de usuarios pendientes.")); }
-            if !eleccion.es_votante(id) { return Err(String::from("No estás registrado como votante."));}
-            if eleccion.es_candidato(id) {return Err("Has sido aceptado como candidato".to_string());}
+            en_fecha(INICIO);
+            como(usuarios[3]);
+            contrato.votar_preferencias(eleccion_id, vec![2, 1]).unwrap();
+            como(administrador());
+            assert_eq!(contrato.procesar_usuario_en_una_eleccion(eleccion_id, usuarios[2], true), Err(Error::VOTACION_INICIADA));
+            assert_eq!(contrato.procesar_usuario_en_una_eleccion(eleccion_id, usuarios[2], false), Ok(()));
 
-            return eleccion.votar_candidato(id, candidato_id);
+            finalizar(&mut contrato, eleccion_id);
+            assert_eq!(contrato.obtener_matriz_preferencias(eleccion_id).unwrap().len(), 2);
+            assert_eq!(ganador(&contrato, eleccion_id), Some(2));
         }
 
-    // ====================================================================
-    // ====================================================================
-    // ====================================================================
+        // ===================================================================================================
+        // =========================mayoría de un referéndum==================================================
+        // ===================================================================================================
 
+        #[test]
+        fn mayoria_simple_es_estricta() {
+            assert_eq!(MAYORIA_REQUERIDA::SIMPLE.alcanzada(51, 100), Ok(true));
+            assert_eq!(MAYORIA_REQUERIDA::SIMPLE.alcanzada(50, 100), Ok(false));
+            assert_eq!(MAYORIA_REQUERIDA::SIMPLE.alcanzada(2, 3), Ok(true));
+        }
 
+        #[test]
+        fn dos_tercios_incluye_el_limite() {
+            assert_eq!(MAYORIA_REQUERIDA::DOS_TERCIOS.alcanzada(20, 30), Ok(true));
+            assert_eq!(MAYORIA_REQUERIDA::DOS_TERCIOS.alcanzada(19, 30), Ok(false));
+            assert_eq!(MAYORIA_REQUERIDA::DOS_TERCIOS.alcanzada(67, 100), Ok(true));
+            assert_eq!(MAYORIA_REQUERIDA::DOS_TERCIOS.alcanzada(66, 100), Ok(false));
+        }
 
-        /// Utilizado por el administrador.
-        /// Permite al administrador transferir el rol de administrador a otra persona.
-        #[ink(message)] //FUNCIONA
-        pub fn transferir_administrador(&mut self, id:AccountId) -> Result<String, String>
-        {
-            if !self.es_administrador() { return Err(ERRORES::NO_ES_ADMINISTRADOR.to_string()); }
-            self.administrador = id;
-            return Ok(String::from("Se transfirió el rol de administrador correctamente."));
+        #[test]
+        fn fraccion_incluye_el_limite() {
+            let tres_quintos = MAYORIA_REQUERIDA::FRACCION { numerador: 3, denominador: 5 };
+            assert_eq!(tres_quintos.alcanzada(60, 100), Ok(true));
+            assert_eq!(tres_quintos.alcanzada(59, 100), Ok(false));
         }
 
-    }
-   
-        
+        /// Referéndum por dos tercios con las opciones "Sí", "No" y "Abstención" y `votantes` votantes, ya en votación.
+        fn referendum_abierto(blancos_en_base:bool, votantes:u8) -> (TrabajoFinal, u64, Vec<AccountId>) {
+            let (mut contrato, usuarios) = contrato_con_usuarios(votantes);
+            let opcion = |texto:&str, sentido:SENTIDO_OPCION| OpcionReferendum { texto: String::from(texto), sentido };
+            let propuesta = Propuesta {
+                pregunta: String::from("¿Se aprueba la reforma del estatuto?"),
+                opciones: vec![opcion("Sí", SENTIDO_OPCION::A_FAVOR), opcion("No", SENTIDO_OPCION::EN_CONTRA), opcion("Abstención", SENTIDO_OPCION::ABSTENCION)],
+                mayoria: MAYORIA_REQUERIDA::DOS_TERCIOS,
+            };
+            let eleccion_id = contrato.crear_referendum(String::from("01-01-2030 10:00"), String::from("01-01-2031 10:00"), blancos_en_base, propuesta).unwrap();
+            contrato.abrir_eleccion(eleccion_id).unwrap();
+            for usuario in usuarios.iter() {
+                como(*usuario);
+                contrato.inscribir_usuario_en_eleccion(eleccion_id, TIPO_DE_USUARIO::VOTANTE).unwrap();
+            }
+            como(administrador());
+            for _ in usuarios.iter() { contrato.procesar_usuarios_en_una_eleccion(eleccion_id, true).unwrap(); }
+            en_fecha(INICIO);
+            (contrato, eleccion_id, usuarios)
+        }
+        /// Como `referendum_abierto`, pero finalizado con `votos` votos para cada opción en ese orden y `blancos`
+        /// votos en blanco.
+        fn referendum_votado(blancos_en_base:bool, votos:[usize; 3], blancos:usize) -> (TrabajoFinal, u64) {
+            let votantes = votos.iter().try_fold(blancos, |total, cantidad| total.checked_add(*cantidad)).unwrap();
+            let (mut contrato, eleccion_id, usuarios) = referendum_abierto(blancos_en_base, u8::try_from(votantes).unwrap());
+            let mut votantes = usuarios.iter();
+            for (opcion_id, cantidad) in (1u64..).zip(votos) {
+                for votante in votantes.by_ref().take(cantidad) {
+                    como(*votante);
+                    contrato.votar_opcion(eleccion_id, opcion_id).unwrap();
+                }
+            }
+            for votante in votantes {
+                como(*votante);
+                contrato.votar_en_blanco(eleccion_id).unwrap();
+            }
+            finalizar(&mut contrato, eleccion_id);
+            (contrato, eleccion_id)
+        }
+
+        /// 4 a favor y 2 en contra alcanzan los dos tercios; la abstención y el blanco no integran la base.
+        #[ink::test]
+        fn referendum_aprobado_sin_los_blancos_en_la_base() {
+            let (contrato, eleccion_id) = referendum_votado(false, [4, 2, 1], 1);
+            let resultado = contrato.obtener_resultado_referendum(eleccion_id).unwrap();
+            assert_eq!(resultado.votos, vec![
+                ConteoOpcion { opcion_id: 1, votos: 4 },
+                ConteoOpcion { opcion_id: 2, votos: 2 },
+                ConteoOpcion { opcion_id: 3, votos: 1 },
+            ]);
+            assert_eq!((resultado.votos_a_favor, resultado.votos_en_contra, resultado.abstenciones), (4, 2, 1));
+            assert_eq!(resultado.base, 6);
+            assert!(resultado.aprobado);
+        }
 
+        /// Con el blanco en la base, 4 de 7 no alcanzan los dos tercios.
+        #[ink::test]
+        fn referendum_rechazado_con_los_blancos_en_la_base() {
+            let (contrato, eleccion_id) = referendum_votado(true, [4, 2, 1], 1);
+            let resultado = contrato.obtener_resultado_referendum(eleccion_id).unwrap();
+            assert_eq!(resultado.base, 7);
+            assert!(!resultado.aprobado);
+        }
 
-    /*#[cfg(test)]
-    mod tests {
-        use super::*;
-    }*/
+        /// Las opciones van de 1 a N, y en un referéndum no se vota a candidatos.
+        #[ink::test]
+        fn referendum_valida_la_opcion() {
+            let (mut contrato, eleccion_id, votantes) = referendum_abierto(false, 1);
+            como(votantes[0]);
+            assert_eq!(contrato.votar_opcion(eleccion_id, 0), Err(Error::OPCION_NO_EXISTE));
+            assert_eq!(contrato.votar_opcion(eleccion_id, 4), Err(Error::OPCION_NO_EXISTE));
+            assert_eq!(contrato.votar_a_candidato(eleccion_id, 1), Err(Error::MODO_VOTACION_INCORRECTO));
+            contrato.votar_opcion(eleccion_id, 3).unwrap();
+            finalizar(&mut contrato, eleccion_id);
+            assert_eq!(contrato.obtener_resultado_referendum(eleccion_id).map(|resultado| resultado.abstenciones), Ok(1));
+        }
+
+        /// Sin votos a favor no se alcanza ninguna mayoría, aunque la base también sea cero.
+        #[test]
+        fn sin_votos_a_favor_nunca_se_alcanza() {
+            assert_eq!(MAYORIA_REQUERIDA::SIMPLE.alcanzada(0, 0), Ok(false));
+            assert_eq!(MAYORIA_REQUERIDA::DOS_TERCIOS.alcanzada(0, 0), Ok(false));
+            assert_eq!(MAYORIA_REQUERIDA::FRACCION { numerador: 0, denominador: 1 }.alcanzada(0, 10), Ok(false));
+        }
+    }
 }
 
@@ -0,0 +1,165 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+#[allow(non_camel_case_types)]
+#[allow(non_snake_case)]
+#[warn(clippy::arithmetic_side_effects)]
+
+#[ink::contract]
+pub mod Reportes {
+    use ink::prelude::vec::Vec;
+    use trabajo_final::TrabajoFinal::{
+        CandidatoConVotos,
+        ESTADO_ELECCION,
+        Error,
+        EstadoVotante,
+        Result,
+        TrabajoFinalRef,
+    };
+
+    /// Participación de una elección.
+    /// El porcentaje se expresa en centésimos de punto: 6789 equivale a 67,89%.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct ReporteParticipacion
+    {
+        pub eleccion_id:u64,
+        pub votantes_registrados:u64,
+        pub votos_emitidos:u64,
+        pub porcentaje_participacion:u64,
+    }
+
+    /// Resultado de un candidato en una elección escrutada.
+    /// En los sistemas con boletas ordenadas, `votos_totales` son las primeras preferencias.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct ResultadoCandidato
+    {
+        pub candidato_id:u64,
+        pub usuario_id:AccountId,
+        pub votos_totales:u64,
+        pub ganador:bool,
+    }
+
+    /// Resultados de una elección escrutada.
+    /// `ganador` es el que determinó el escrutinio según el sistema de la elección. Es None si hubo empate,
+    /// si la elección reparte bancas o si se convocó a una segunda vuelta, cuyo id queda en `segunda_vuelta`.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct ReporteResultados
+    {
+        pub eleccion_id:u64,
+        pub ganador:Option<u64>,
+        pub segunda_vuelta:Option<u64>,
+        pub candidatos:Vec<ResultadoCandidato>,
+    }
+
+    /// Votos en blanco y nulos de una elección.
+    /// Los porcentajes son sobre los votos emitidos y se expresan en centésimos de punto.
+    #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct ReporteBlancosYNulos
+    {
+        pub eleccion_id:u64,
+        pub votos_emitidos:u64,
+        pub votos_en_blanco:u64,
+        pub votos_nulos:u64,
+        pub porcentaje_en_blanco:u64,
+        pub porcentaje_nulos:u64,
+    }
+
+    /// Contrato de reportes.
+    /// Consulta a TrabajoFinal, que debe tenerlo autorizado mediante `establecer_contrato_reportes`.
+    #[ink(storage)]
+    pub struct Reportes {
+        trabajo_final:TrabajoFinalRef,
+    }
+
+    impl Reportes {
+
+        #[ink(constructor)]
+        pub fn new(trabajo_final:AccountId) -> Self {
+            Self {
+                trabajo_final: ink::env::call::FromAccountId::from_account_id(trabajo_final),
+            }
+        }
+
+        /// Calcula el porcentaje de `parte` sobre `total` en centésimos de punto, redondeando hacia abajo.
+        fn porcentaje(parte:u64, total:u64) -> Result<u64> {
+            if total == 0 { return Ok(0); }
+            let escalado = (parte as u128).checked_mul(10_000).ok_or(Error::OVERFLOW)?;
+            let porcentaje = escalado.checked_div(total as u128).ok_or(Error::OVERFLOW)?;
+            u64::try_from(porcentaje).map_err(|_| Error::OVERFLOW)
+        }
+
+        /// Devuelve una página de los votantes registrados en la elección y si ya votaron,
+        /// paginada como `obtener_padron_eleccion` de TrabajoFinal.
+        #[ink(message)]
+        pub fn reporte_votantes_registrados(&self, eleccion_id:u64, desde:u64, cantidad:u64) -> Result<Vec<EstadoVotante>>
+        {
+            self.trabajo_final.obtener_padron_eleccion(eleccion_id, desde, cantidad)
+        }
+
+        /// Devuelve cuántos votantes registrados emitieron su voto y el porcentaje de participación.
+        /// Usa los contadores de la elección, sin recorrer el padrón.
+        #[ink(message)]
+        pub fn reporte_participacion(&self, eleccion_id:u64) -> Result<ReporteParticipacion>
+        {
+            let resumen = self.trabajo_final.obtener_resumen_eleccion(eleccion_id)?;
+            let votantes_registrados = resumen.cantidad_votantes;
+            let votos_emitidos = resumen.votos_emitidos;
+
+            Ok(ReporteParticipacion {
+                eleccion_id,
+                votantes_registrados,
+                votos_emitidos,
+                porcentaje_participacion: Self::porcentaje(votos_emitidos, votantes_registrados)?,
+            })
+        }
+
+        /// Devuelve los resultados de una elección ya escrutada, con los candidatos ordenados de mayor a menor
+        /// cantidad de votos. A igual cantidad de votos se ordena por id de candidato.
+        #[ink(message)]
+        pub fn reporte_resultados(&self, eleccion_id:u64) -> Result<ReporteResultados>
+        {
+            let resumen = self.trabajo_final.obtener_resumen_eleccion(eleccion_id)?;
+            if resumen.estado != ESTADO_ELECCION::FINALIZADA || !resumen.escrutada { return Err(Error::ELECCION_NO_FINALIZADA); }
+
+            let mut conteo:Vec<CandidatoConVotos> = self.trabajo_final.obtener_conteo_eleccion(eleccion_id)?;
+            conteo.sort_by(|a, b| b.votos_totales.cmp(&a.votos_totales).then(a.id.cmp(&b.id)));
+
+            Ok(ReporteResultados {
+                eleccion_id,
+                ganador: resumen.ganador,
+                segunda_vuelta: resumen.segunda_vuelta,
+                candidatos: conteo.into_iter().map(|candidato| ResultadoCandidato {
+                    candidato_id: candidato.id,
+                    usuario_id: candidato.usuario_id,
+                    votos_totales: candidato.votos_totales,
+                    ganador: resumen.ganador == Some(candidato.id),
+                }).collect(),
+            })
+        }
+
+        /// Devuelve los votos en blanco y nulos de una elección cuyo conteo ya está disponible.
+        #[ink(message)]
+        pub fn reporte_blancos_y_nulos(&self, eleccion_id:u64) -> Result<ReporteBlancosYNulos>
+        {
+            let blancos_y_nulos = self.trabajo_final.obtener_votos_blancos_y_nulos(eleccion_id)?;
+            let votos_emitidos = self.trabajo_final.obtener_resumen_eleccion(eleccion_id)?.votos_emitidos;
+
+            Ok(ReporteBlancosYNulos {
+                eleccion_id,
+                votos_emitidos,
+                votos_en_blanco: blancos_y_nulos.votos_en_blanco,
+                votos_nulos: blancos_y_nulos.votos_nulos,
+                porcentaje_en_blanco: Self::porcentaje(blancos_y_nulos.votos_en_blanco, votos_emitidos)?,
+                porcentaje_nulos: Self::porcentaje(blancos_y_nulos.votos_nulos, votos_emitidos)?,
+            })
+        }
+
+        #[ink(message)]
+        pub fn obtener_contrato_trabajo_final(&self) -> AccountId
+        {
+            ink::ToAccountId::to_account_id(&self.trabajo_final)
+        }
+    }
+}